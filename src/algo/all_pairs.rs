@@ -0,0 +1,291 @@
+use crate::{EdgeWeight, EdgeWeights, Graph, Path, ShortestPathError, ShortestPaths, Weight};
+
+/// Shortest distances between all pairs of verticles with paths between them,
+/// see [Graph::floyd_warshall] and [Graph::johnson]
+#[derive(Debug)]
+pub struct DistanceMatrix<'gr, VD, EL, W>
+{
+    graph: &'gr Graph<VD, EL>,
+    distance: Vec<Vec<Option<W>>>,
+    // the last edge of the shortest path between a pair as (from, edge_idx)
+    parent: Vec<Vec<Option<(usize, usize)>>>,
+}
+
+impl<'gr, VD, EL, W> DistanceMatrix<'gr, VD, EL, W>
+    where W: Weight,
+{
+    /// Length of the shortest path from `from` to `to`, `None` if `to` is unreachable
+    pub fn distance(&self, from: usize, to: usize) -> Option<W>
+    {
+        self.distance.get(from)
+            .and_then(|row| row.get(to))
+            .copied()
+            .flatten()
+    }
+
+    /// Distances from `from` to every verticle
+    ///
+    /// # Panics
+    /// If there is no verticle `from`, use [DistanceMatrix::distance] for unchecked ids
+    pub fn row(&self, from: usize) -> &[Option<W>]
+    { &self.distance[from] }
+
+    /// Shortest path from `from` to `to`, `None` if `to` is unreachable
+    pub fn path(&self, from: usize, to: usize) -> Option<Path<'gr, VD, EL>>
+    {
+        self.distance(from, to)?;
+        Some(Path::from_parents(self.graph, to, |curr| when! {
+            curr == from => None,
+            _ => self.parent[from][curr],
+        }))
+    }
+}
+
+type MatrixResult<'gr, VD, EL, W, E> = Result<DistanceMatrix<'gr, VD, EL, W>, ShortestPathError<'gr, VD, EL, W, E>>;
+
+/// Weight of an edge reweighted by potentials of its ends, as done by Johnson's algorithm.
+/// With potentials being shortest distances it's never negative, but floats may get
+/// slightly below zero, so it's clamped to zero
+pub (crate) fn reduced_weight<W: Weight>(weight: W, from_potential: W, to_potential: W) -> W
+{
+    let reduced = weight + from_potential - to_potential;
+    when! {
+        reduced < W::zero() => W::zero(),
+        _ => reduced,
+    }
+}
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// All-pairs shortest paths by Floyd–Warshall algorithm. Takes cubic time regardless
+    /// of the number of edges, so suits dense graphs. Edges may have negative weights,
+    /// fails with a negative cycle if there is one
+    pub fn floyd_warshall<WF>(&self, weight: WF) -> MatrixResult<'_, VD, EL, WF::Weight, WF::Error>
+        where WF: EdgeWeight<EL>,
+    {
+        let weights = self.edge_weights(weight)?;
+        let cnt = self.verts_cnt();
+
+        let mut distance = vec![vec![None; cnt]; cnt];
+        let mut parent = vec![vec![None; cnt]; cnt];
+        (0..cnt).for_each(|id| distance[id][id] = Some(WF::Weight::zero()));
+        self.verts()
+            .for_each(|from| from.edges()
+                .zip(weights.of(from.id()))
+                .enumerate()
+                .for_each(|(edge_idx, ((_, to), weight))| {
+                    if distance[from.id()][to.id()].is_none_or(|dist| *weight < dist) {
+                        distance[from.id()][to.id()] = Some(*weight);
+                        parent[from.id()][to.id()] = Some((from.id(), edge_idx));
+                    }
+                }));
+
+        // a verticle, which is shorter than nothing to get to, lies on a negative cycle.
+        // Distances through it only go down, so it's reported before they overflow
+        let is_negative = |dist: Option<WF::Weight>| dist.is_some_and(|dist| dist < WF::Weight::zero());
+        let mut on_cycle = (0..cnt).any(|id| is_negative(distance[id][id]));
+        'relax: for mid in 0..cnt {
+            if on_cycle {
+                break
+            }
+            for from in 0..cnt {
+                let Some(to_mid) = distance[from][mid] else {
+                    continue
+                };
+                for to in 0..cnt {
+                    let Some(from_mid) = distance[mid][to] else {
+                        continue
+                    };
+                    let through = to_mid + from_mid;
+                    if distance[from][to].is_none_or(|dist| through < dist) {
+                        distance[from][to] = Some(through);
+                        parent[from][to] = parent[mid][to];
+                    }
+                }
+                if is_negative(distance[from][from]) {
+                    on_cycle = true;
+                    break 'relax
+                }
+            }
+        }
+
+        if on_cycle {
+            let cycle = self.bellman_ford_with(&mut vec![Some(WF::Weight::zero()); cnt], &weights)
+                .expect_err("a negative cycle is reachable from some verticle, as all of them are sources");
+            return Err(ShortestPathError::NegativeCycle(cycle))
+        }
+        Ok(DistanceMatrix{ graph: self, distance, parent })
+    }
+
+    /// All-pairs shortest paths by Johnson's algorithm: edges are reweighed to be non-negative
+    /// by a single Bellman–Ford run, then Dijkstra runs from every verticle. Suits sparse graphs.
+    /// Fails with a negative cycle if there is one
+    pub fn johnson<WF>(&self, weight: WF) -> MatrixResult<'_, VD, EL, WF::Weight, WF::Error>
+        where WF: EdgeWeight<EL>,
+    {
+        let weights = self.edge_weights(weight)?;
+        let zero = WF::Weight::zero();
+
+        // distances from an imaginary verticle with edges of zero weight to all the others
+        let mut potential = vec![Some(zero); self.verts_cnt()];
+        self.bellman_ford_with(&mut potential, &weights)
+            .map_err(ShortestPathError::NegativeCycle)?;
+        let potential: Vec<WF::Weight> = potential.into_iter()
+            .map(|h| h.expect("every verticle is reachable from the imaginary one"))
+            .collect();
+
+        let reweighted = self.verts()
+            .map(|from| from.edges()
+                .zip(weights.of(from.id()))
+                .map(|((_, to), weight)| reduced_weight(*weight, potential[from.id()], potential[to.id()]))
+                .collect())
+            .collect();
+        let reweighted = EdgeWeights{ weights: reweighted };
+
+        let (distance, parent) = (0..self.verts_cnt())
+            .map(|from| {
+                let ShortestPaths{ distance, parent, .. } = self.dijkstra_with(from, &reweighted, None);
+                let distance = distance.into_iter()
+                    .zip(&potential)
+                    .map(|(dist, h)| dist.map(|dist| dist + *h - potential[from]))
+                    .collect();
+                (distance, parent)
+            })
+            .unzip();
+        Ok(DistanceMatrix{ graph: self, distance, parent })
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{test_util::months, read_tgf, ParseWeight};
+
+    use super::*;
+
+    fn network() -> Graph<String, String>
+    {
+        let input = r"
+            1 a
+            2 b
+            3 c
+            4 d
+            5 e
+            #
+            1 2 4
+            1 3 1
+            3 2 -2
+            2 4 5
+            3 4 8
+            4 1 1
+            4 4 0
+            2 4 2
+        ";
+        read_tgf(input.lines())
+            .expect("input is valid")
+    }
+
+    fn assert_same<VD, EL>(fst: &DistanceMatrix<'_, VD, EL, i64>, snd: &DistanceMatrix<'_, VD, EL, i64>, cnt: usize)
+    {
+        (0..cnt).for_each(|from| (0..cnt).for_each(|to| {
+            assert_eq!(fst.distance(from, to), snd.distance(from, to), "from {from} to {to}");
+            let length = |matrix: &DistanceMatrix<'_, VD, EL, i64>| matrix.path(from, to)
+                .map(|path| path.len());
+            assert_eq!(length(fst).is_some(), length(snd).is_some());
+        }));
+    }
+
+    #[test]
+    fn matrix()
+    {
+        let graph = network();
+        let floyd = graph.floyd_warshall(ParseWeight::<i64>::new())
+            .expect("there are no negative cycles");
+        let johnson = graph.johnson(ParseWeight::<i64>::new())
+            .expect("there are no negative cycles");
+        assert_same(&floyd, &johnson, graph.verts_cnt());
+
+        assert_eq!(floyd.row(0), [Some(0), Some(-1), Some(1), Some(1), None]);
+        assert_eq!(floyd.distance(3, 1), Some(0));
+        assert_eq!(floyd.distance(4, 0), None);
+        assert_eq!(floyd.distance(4, 4), Some(0));
+        assert_eq!(floyd.distance(5, 0), None);
+
+        [&floyd, &johnson].into_iter()
+            .for_each(|matrix| {
+                let path = matrix.path(3, 1)
+                    .expect("b is reachable from d");
+                assert_eq!(path.ids(), [3, 0, 2, 1]);
+                assert_eq!(path.labels(), [&"1", &"1", &"-2"]);
+                // the cheaper one of parallel edges
+                let path = matrix.path(1, 3)
+                    .expect("d is reachable from b");
+                assert_eq!(path.labels(), [&"2"]);
+
+                let path = matrix.path(2, 2)
+                    .expect("c is reachable from itself");
+                assert!(path.is_empty());
+                assert!(matrix.path(0, 4).is_none());
+            });
+    }
+
+    #[test]
+    fn matches_dijkstra()
+    {
+        let graph = months();
+        let weight = |label: &String| label.len() as i64 + 1;
+
+        let floyd = graph.floyd_warshall(weight)
+            .expect("weights are positive");
+        let johnson = graph.johnson(weight)
+            .expect("weights are positive");
+        assert_same(&floyd, &johnson, graph.verts_cnt());
+
+        graph.all_pairs_dijkstra(weight)
+            .expect("weights are positive")
+            .iter()
+            .enumerate()
+            .for_each(|(from, paths)| (0..graph.verts_cnt())
+                .for_each(|to| assert_eq!(paths.distance(to), floyd.distance(from, to))));
+    }
+
+    #[test]
+    fn negative_cycle()
+    {
+        let mut graph = network();
+        graph.set_label(3, 0, "-4".into())
+            .expect("edge exists");
+
+        let errors = [graph.floyd_warshall(ParseWeight::<i64>::new()), graph.johnson(ParseWeight::<i64>::new())];
+        errors.into_iter()
+            .for_each(|res| match res {
+                Err(ShortestPathError::NegativeCycle(cycle)) => {
+                    assert!(cycle.weight() < 0);
+                    assert_eq!(cycle.cycle().source().id(), cycle.cycle().target().id());
+                },
+                Err(err) => panic!("Unexpected error: {err}"),
+                Ok(_) => panic!("There is a negative cycle"),
+            });
+    }
+
+    #[test]
+    fn dense_negative()
+    {
+        // every pair is connected both ways, so distances would keep doubling
+        // with every verticle passed through and overflow before the end
+        let mut graph = Graph::<(), i32>::new();
+        graph.extend(std::iter::repeat_n((), 64));
+        (0..64).for_each(|from| (0..64)
+            .filter(|to| *to != from)
+            .for_each(|to| {
+                graph.add_edge(from, to, -1)
+                    .expect("verts exist");
+            }));
+
+        match graph.floyd_warshall(|label: &i32| *label) {
+            Err(ShortestPathError::NegativeCycle(cycle)) => assert!(cycle.weight() < 0),
+            Err(err) => panic!("Unexpected error: {err:?}"),
+            Ok(_) => panic!("There is a negative cycle"),
+        }
+    }
+}
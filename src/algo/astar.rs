@@ -0,0 +1,174 @@
+use std::collections::BinaryHeap;
+
+use super::dijkstra::MinFirst;
+use crate::{EdgeWeight, Graph, Path, VertHandle, Weight, WeightError, WeightErrorKind, WeightResult};
+
+type PairResult<'gr, VD, EL, W, E> = WeightResult<Option<(W, Path<'gr, VD, EL>)>, E>;
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Shortest path from `from` to `to` along with its length, `None` if `to` is unreachable.
+    ///
+    /// `heuristic` estimates the distance from a verticle to `to`. The path is the shortest one
+    /// as long as it never overestimates, the better it estimates, the less verticles are explored.
+    /// It's called at most once per verticle. Only edges of explored verticles are weighed,
+    /// so failing and negative weights are reported only if the search comes across them
+    pub fn astar<'gr, WF, H>(&'gr self, from: VertHandle<'gr, VD, EL>, to: VertHandle<'gr, VD, EL>, mut weight: WF, mut heuristic: H)
+        -> PairResult<'gr, VD, EL, WF::Weight, WF::Error>
+        where WF: EdgeWeight<EL>,
+              H: FnMut(VertHandle<'gr, VD, EL>) -> WF::Weight,
+    {
+        assert!(from.is_of(self), "'from' is from  different owner");
+        assert!(to.is_of(self), "'to' is from  different owner");
+
+        let mut distance: Vec<Option<WF::Weight>> = vec![None; self.verts_cnt()];
+        let mut estimate: Vec<Option<WF::Weight>> = vec![None; self.verts_cnt()];
+        let mut parent = vec![None; self.verts_cnt()];
+        let mut estimate_of = |vert: VertHandle<'gr, VD, EL>| *estimate[vert.id()]
+            .get_or_insert_with(|| heuristic(vert));
+
+        distance[from.id()] = Some(WF::Weight::zero());
+        let mut queue = BinaryHeap::from([MinFirst{ priority: estimate_of(from), vert: from.id() }]);
+
+        while let Some(MinFirst{ priority, vert }) = queue.pop() {
+            let dist = distance[vert].expect("queued verticles have distance");
+            let vert = self.get_vert(vert)
+                .expect("queue holds valid verticles");
+            // the entry is outdated if the verticle was reached by a shorter path since then
+            if priority > dist + estimate_of(vert) {
+                continue
+            }
+            if vert.id() == to.id() {
+                let path = Path::from_parents(self, to.id(), |curr| parent[curr]);
+                return Ok(Some((dist, path)))
+            }
+
+            for (edge_idx, (label, next)) in vert.edges().enumerate() {
+                let error = |kind| WeightError::new(vert.id(), edge_idx, kind);
+                let w = weight.weight(label)
+                    .map_err(|err| error(WeightErrorKind::Label(err)))?;
+                match w.partial_cmp(&WF::Weight::zero()) {
+                    None => return Err(error(WeightErrorKind::Incomparable)),
+                    Some(std::cmp::Ordering::Less) => return Err(error(WeightErrorKind::Negative)),
+                    _ => (),
+                }
+
+                let through = dist + w;
+                if distance[next.id()].is_some_and(|dist| dist <= through) {
+                    continue
+                }
+                distance[next.id()] = Some(through);
+                parent[next.id()] = Some((vert.id(), edge_idx));
+                queue.push(MinFirst{ priority: through + estimate_of(next), vert: next.id() });
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::try_weight;
+
+    use super::*;
+
+    // rooms of a floor plan with their coordinates, corridors are labeled by their length
+    fn floor() -> Graph<(&'static str, f64, f64), f64>
+    {
+        let mut graph = Graph::new();
+        graph.extend([
+            ("hall", 0.0, 0.0),
+            ("kitchen", 3.0, 0.0),
+            ("stairs", 3.0, 4.0),
+            ("balcony", 6.0, 4.0),
+            ("closet", -1.0, 0.0),
+        ]);
+        [(0, 1, 3.0), (1, 2, 4.0), (0, 2, 6.0), (2, 3, 3.0), (1, 3, 9.0), (0, 4, 1.0), (4, 0, 1.0)]
+            .into_iter()
+            .for_each(|(from, to, len)| {
+                graph.add_edge(from, to, len)
+                    .expect("verts exist");
+            });
+        graph
+    }
+
+    fn straight_line<'gr>(target: VertHandle<'gr, (&'static str, f64, f64), f64>)
+        -> impl FnMut(VertHandle<'gr, (&'static str, f64, f64), f64>) -> f64
+    {
+        move |vert| {
+            let (_, x, y) = **vert;
+            let (_, tx, ty) = **target;
+            ((x - tx).powi(2) + (y - ty).powi(2)).sqrt()
+        }
+    }
+
+    #[test]
+    fn floor_plan()
+    {
+        let graph = floor();
+        let hall = graph.get_vert(0).expect("vert exists");
+        let balcony = graph.get_vert(3).expect("vert exists");
+
+        let (cost, path) = graph.astar(hall, balcony, |len: &f64| *len, straight_line(balcony))
+            .expect("lengths are valid")
+            .expect("balcony is reachable");
+        assert_eq!(cost, 9.0);
+        assert_eq!(path.ids(), [0, 2, 3]);
+
+        // the same as Dijkstra, which is A* without a heuristic
+        let (dijkstra_cost, _) = graph.dijkstra_to(hall, balcony, |len: &f64| *len)
+            .expect("lengths are valid")
+            .expect("balcony is reachable");
+        assert_eq!(cost, dijkstra_cost);
+        let (zero_cost, _) = graph.astar(hall, balcony, |len: &f64| *len, |_| 0.0)
+            .expect("lengths are valid")
+            .expect("balcony is reachable");
+        assert_eq!(cost, zero_cost);
+
+        let closet = graph.get_vert(4).expect("vert exists");
+        assert!(graph.astar(balcony, closet, |len: &f64| *len, straight_line(closet))
+            .expect("lengths are valid")
+            .is_none());
+    }
+
+    #[test]
+    fn heuristic_is_called_once_per_verticle()
+    {
+        let graph = floor();
+        let hall = graph.get_vert(0).expect("vert exists");
+        let balcony = graph.get_vert(3).expect("vert exists");
+
+        let mut calls = vec![0; graph.verts_cnt()];
+        let mut line = straight_line(balcony);
+        graph.astar(hall, balcony, |len: &f64| *len, |vert| {
+                calls[vert.id()] += 1;
+                line(vert)
+            })
+            .expect("lengths are valid");
+        assert!(calls.iter().all(|cnt| *cnt <= 1));
+    }
+
+    #[test]
+    fn unexplored_edges_are_not_weighed()
+    {
+        let graph = floor();
+        let hall = graph.get_vert(0).expect("vert exists");
+        let closet = graph.get_vert(4).expect("vert exists");
+        let short_only = || try_weight(|len: &f64| when! {
+            *len > 5.0 => Err("too long"),
+            _ => Ok(*len),
+        });
+
+        // the search stops at the hall before weighing its corridors
+        let (cost, _) = graph.astar(closet, hall, short_only(), |_| 0.0)
+            .expect("only the closet's corridor is weighed")
+            .expect("hall is reachable");
+        assert_eq!(cost, 1.0);
+
+        let err = graph.astar(hall, closet, short_only(), |_| 0.0)
+            .expect_err("hall has a long corridor");
+        assert_eq!((err.from(), err.edge_idx()), (0, 1));
+        assert!(matches!(err.kind(), WeightErrorKind::Label("too long")));
+    }
+}
@@ -0,0 +1,267 @@
+use std::{error::Error, fmt::{Debug, Display}};
+
+use super::dijkstra::Parents;
+use crate::{EdgeWeight, EdgeWeights, Graph, Path, ShortestPaths, VertHandle, Weight, WeightError};
+
+/// Cycle of negative total weight, which makes shortest paths through it undefined
+#[derive(Debug)]
+pub struct NegativeCycle<'gr, VD, EL, W>
+{
+    cycle: Path<'gr, VD, EL>,
+    weight: W,
+}
+
+impl<'gr, VD, EL, W> NegativeCycle<'gr, VD, EL, W>
+    where W: Weight,
+{
+    /// Closed path, which starts and ends with the same verticle
+    pub fn cycle(&self) -> &Path<'gr, VD, EL>
+    { &self.cycle }
+
+    pub fn into_cycle(self) -> Path<'gr, VD, EL>
+    { self.cycle }
+
+    /// Total weight of the cycle, which is negative
+    pub fn weight(&self) -> W
+    { self.weight }
+}
+
+/// Error of shortest path algorithms allowing negative weights, such as [Graph::bellman_ford]
+#[derive(Debug)]
+pub enum ShortestPathError<'gr, VD, EL, W, E>
+{
+    Weight(WeightError<E>),
+    NegativeCycle(NegativeCycle<'gr, VD, EL, W>),
+}
+
+impl<VD, EL, W, E> From<WeightError<E>> for ShortestPathError<'_, VD, EL, W, E>
+{
+    fn from(err: WeightError<E>) -> Self
+    { ShortestPathError::Weight(err) }
+}
+
+impl<VD, EL, W, E> Display for ShortestPathError<'_, VD, EL, W, E>
+    where VD: Display,
+          EL: Display,
+          W: Debug,
+          E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self {
+            ShortestPathError::Weight(err) => Display::fmt(err, f),
+            ShortestPathError::NegativeCycle(NegativeCycle{ cycle, weight }) => {
+                f.write_fmt(format_args!("Cycle of weight {:?}: {}", weight, **cycle.source()))?;
+                cycle.edges()
+                    .try_for_each(|(_, label, to)| f.write_fmt(format_args!(" -[{}]-> {}", label, **to)))
+            },
+        }
+    }
+}
+
+impl<VD, EL, W, E> Error for ShortestPathError<'_, VD, EL, W, E>
+    where VD: Debug + Display,
+          EL: Debug + Display,
+          W: Debug,
+          E: Debug + Display,
+{}
+
+type BellmanFordResult<'gr, VD, EL, W, E> = Result<ShortestPaths<'gr, VD, EL, W>, ShortestPathError<'gr, VD, EL, W, E>>;
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Shortest paths from `from`, where edges may have negative weights.
+    /// Fails with a negative cycle if there is one reachable from `from`
+    pub fn bellman_ford<'gr, WF>(&'gr self, from: VertHandle<'gr, VD, EL>, weight: WF)
+        -> BellmanFordResult<'gr, VD, EL, WF::Weight, WF::Error>
+        where WF: EdgeWeight<EL>,
+    {
+        assert!(from.is_of(self), "'from' is from  different owner");
+
+        let weights = self.edge_weights(weight)?;
+        let mut distance = vec![None; self.verts_cnt()];
+        distance[from.id()] = Some(WF::Weight::zero());
+
+        let parent = self.bellman_ford_with(&mut distance, &weights)
+            .map_err(ShortestPathError::NegativeCycle)?;
+        Ok(ShortestPaths{ graph: self, source: from.id(), distance, parent })
+    }
+
+    /// Relaxes all the edges until `distance` stops changing, starting from the verticles,
+    /// which already have it. Returns parents of the verticles as `(from, edge_idx)`
+    pub (crate) fn bellman_ford_with<W>(&self, distance: &mut [Option<W>], weights: &EdgeWeights<W>)
+        -> Result<Parents, NegativeCycle<'_, VD, EL, W>>
+        where W: Weight,
+    { self.bellman_ford_through(distance, weights, |_, _, _| true) }
+
+    /// Same as [Graph::bellman_ford_with], but follows only edges `(from, edge_idx, to)`,
+    /// which are `allowed`, so only they may make a negative cycle
+    pub (crate) fn bellman_ford_through<W, F>(&self, distance: &mut [Option<W>], weights: &EdgeWeights<W>, mut allowed: F)
+        -> Result<Parents, NegativeCycle<'_, VD, EL, W>>
+        where W: Weight,
+              F: FnMut(usize, usize, usize) -> bool,
+    {
+        let mut parent = vec![None; self.verts_cnt()];
+
+        // without negative cycles every shortest path has less edges than there are verticles,
+        // so a change on the last round means there is one
+        let mut changed = None;
+        for _ in 0..self.verts_cnt() {
+            changed = None;
+            self.verts()
+                .for_each(|from| {
+                    let Some(dist) = distance[from.id()] else {
+                        return
+                    };
+                    from.edges()
+                        .zip(weights.of(from.id()))
+                        .enumerate()
+                        .filter(|(edge_idx, ((_, to), _))| allowed(from.id(), *edge_idx, to.id()))
+                        .for_each(|(edge_idx, ((_, to), weight))| {
+                            let through = dist + *weight;
+                            if distance[to.id()].is_none_or(|dist| through < dist) {
+                                distance[to.id()] = Some(through);
+                                parent[to.id()] = Some((from.id(), edge_idx));
+                                changed = Some(to.id());
+                            }
+                        });
+                });
+            if changed.is_none() {
+                break
+            }
+        }
+
+        match changed {
+            Some(changed) => Err(self.cycle_through_parents(changed, &parent, weights)),
+            None => Ok(parent),
+        }
+    }
+
+    /// Negative cycle, which parents of `changed` lead to
+    fn cycle_through_parents<W>(&self, changed: usize, parent: &[Option<(usize, usize)>], weights: &EdgeWeights<W>)
+        -> NegativeCycle<'_, VD, EL, W>
+        where W: Weight,
+    {
+        // going back as many times as there are verticles surely ends up on the cycle
+        let mut on_cycle = changed;
+        for _ in 0..self.verts_cnt() {
+            on_cycle = parent[on_cycle].expect("changed verticle has parents").0;
+        }
+
+        let mut edges = vec![];
+        let mut curr = on_cycle;
+        loop {
+            let (from, edge_idx) = parent[curr].expect("verticles on cycle have parents");
+            edges.push((from, edge_idx));
+            curr = from;
+            if curr == on_cycle {
+                break
+            }
+        }
+
+        let start = self.get_vert(on_cycle)
+            .expect("cycle goes through valid verticles");
+        let (cycle, weight) = edges.into_iter()
+            .rev()
+            .fold((Path::new(start), W::zero()), |(mut path, weight), (from, edge_idx)| {
+                let (label, to) = path.target()
+                    .edge(edge_idx)
+                    .expect("cycle goes through valid edges");
+                path.push(label, to);
+                let edge_weight = weights.get(from, edge_idx)
+                    .expect("every edge is weighed");
+                (path, weight + edge_weight)
+            });
+        NegativeCycle{ cycle, weight }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{read_tgf, ParseWeight};
+
+    use super::*;
+
+    fn trades() -> Graph<String, String>
+    {
+        let input = r"
+            1 Gold
+            2 Silver
+            3 Copper
+            4 Iron
+            #
+            1 2 3
+            2 3 -2
+            1 3 2
+            3 4 1
+            4 2 4
+        ";
+        read_tgf(input.lines())
+            .expect("input is valid")
+    }
+
+    #[test]
+    fn negative_weights()
+    {
+        let graph = trades();
+        let gold = graph.get_vert(0).expect("vert exists");
+        let paths = graph.bellman_ford(gold, ParseWeight::<i32>::new())
+            .expect("there are no negative cycles");
+
+        let distances: Vec<Option<i32>> = (0..graph.verts_cnt())
+            .map(|id| paths.distance(id))
+            .collect();
+        assert_eq!(distances, [Some(0), Some(3), Some(1), Some(2)]);
+        assert_eq!(paths.path_to(3).map(|path| path.ids()), Some(vec![0, 1, 2, 3]));
+
+        // Dijkstra refuses such weights
+        assert!(graph.dijkstra(gold, ParseWeight::<i32>::new()).is_err());
+    }
+
+    #[test]
+    fn negative_cycle()
+    {
+        let mut graph = trades();
+        // Iron -> Silver gets cheaper, so Silver -> Copper -> Iron -> Silver costs -2
+        graph.set_label(3, 0, "-1".into())
+            .expect("edge exists");
+        let gold = graph.get_vert(0).expect("vert exists");
+
+        let err = graph.bellman_ford(gold, ParseWeight::<i32>::new())
+            .expect_err("there is a negative cycle");
+        let ShortestPathError::NegativeCycle(cycle) = err else {
+            panic!("Unexpected error: {err}")
+        };
+        assert_eq!(cycle.weight(), -2);
+
+        let cycle = cycle.into_cycle();
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(cycle.source().id(), cycle.target().id());
+        let mut ids = cycle.ids();
+        ids.pop();
+        ids.sort();
+        assert_eq!(ids, [1, 2, 3]);
+    }
+
+    #[test]
+    fn unreachable_cycle_is_ignored()
+    {
+        let mut graph = Graph::<&str, f64>::new();
+        graph.extend(["start", "end", "loop"]);
+        graph.add_edge(0, 1, 1.5)
+            .and_then(|g| g.add_edge(2, 2, -1.0))
+            .expect("verts exist");
+        let start = graph.get_vert(0).expect("vert exists");
+
+        let paths = graph.bellman_ford(start, |label: &f64| *label)
+            .expect("the loop isn't reachable");
+        assert_eq!(paths.distance(1), Some(1.5));
+        assert!(!paths.is_reachable(2));
+
+        let start = graph.get_vert(2).expect("vert exists");
+        let err = graph.bellman_ford(start, |label: &f64| *label)
+            .expect_err("the loop is negative");
+        assert_eq!(err.to_string(), "Cycle of weight -1.0: loop -[-1]-> loop");
+    }
+}
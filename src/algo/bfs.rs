@@ -0,0 +1,361 @@
+use std::collections::VecDeque;
+
+use crate::{Graph, Path, VertHandle};
+
+/// Verticle visited by [Graph::bfs_steps] along with how it was reached
+#[derive(Debug)]
+pub struct BfsStep<'gr, VD, EL>
+{
+    vert: VertHandle<'gr, VD, EL>,
+    depth: usize,
+    parent: Option<(VertHandle<'gr, VD, EL>, usize)>,
+}
+
+impl<VD, EL> Clone for BfsStep<'_, VD, EL>
+{
+    fn clone(&self) -> Self
+    { *self }
+}
+
+impl<VD, EL> Copy for BfsStep<'_, VD, EL> {}
+
+impl<'gr, VD, EL> BfsStep<'gr, VD, EL>
+{
+    pub fn vert(&self) -> VertHandle<'gr, VD, EL>
+    { self.vert }
+
+    /// Number of edges from the root of the traversal
+    pub fn depth(&self) -> usize
+    { self.depth }
+
+    /// Whether the traversal started over from this verticle
+    pub fn is_root(&self) -> bool
+    { self.parent.is_none() }
+
+    /// Verticle the edge to this one was followed from
+    pub fn parent(&self) -> Option<VertHandle<'gr, VD, EL>>
+    { self.parent.map(|(parent, _)| parent) }
+
+    /// Index of the followed edge among the parent's edges
+    pub fn edge_idx(&self) -> Option<usize>
+    { self.parent.map(|(_, edge_idx)| edge_idx) }
+
+    /// Label of the followed edge
+    pub fn label(&self) -> Option<&'gr EL>
+    {
+        self.parent
+            .and_then(|(parent, edge_idx)| parent.edge(edge_idx))
+            .map(|(label, _)| label)
+    }
+}
+
+/// Same traversal as [BFSIterator](crate::BFSIterator), but tells how every verticle was reached
+pub struct BFSStepIterator<'gr, VD, EL>
+{
+    graph: &'gr Graph<VD, EL>,
+    marked: Vec<bool>,
+    queue: VecDeque<BfsStep<'gr, VD, EL>>,
+    last_root: Option<usize>,
+    // limited traversal doesn't look for other roots
+    max_depth: Option<usize>,
+}
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Same as [Graph::bfs], but yields depth and parent of every verticle.
+    /// Depth is counted from the current root, roots have no parent
+    pub fn bfs_steps(&self) -> BFSStepIterator<'_, VD, EL>
+    {
+        BFSStepIterator {
+            graph: self,
+            marked: vec![false; self.verts_cnt()],
+            queue: VecDeque::new(),
+            last_root: None,
+            max_depth: None,
+        }
+    }
+
+    /// Same as [Graph::bfs_from], but yields depth and parent of every verticle
+    pub fn bfs_steps_from<'gr>(&'gr self, from: VertHandle<'gr, VD, EL>) -> BFSStepIterator<'gr, VD, EL>
+    {
+        assert!(from.is_of(self), "'from' is from  different owner");
+
+        let mut bfs = self.bfs_steps();
+        bfs.marked[from.id()] = true;
+        bfs.queue.push_back(BfsStep{ vert: from, depth: 0, parent: None });
+        bfs
+    }
+
+    /// Verticles within `max_depth` edges from `from` along with how they were reached.
+    /// Unlike [Graph::bfs_steps_from], it doesn't go on to other verticles
+    pub fn bfs_steps_within<'gr>(&'gr self, from: VertHandle<'gr, VD, EL>, max_depth: usize) -> BFSStepIterator<'gr, VD, EL>
+    {
+        let mut bfs = self.bfs_steps_from(from);
+        bfs.max_depth = Some(max_depth);
+        bfs
+    }
+
+    /// Tree of shortest (by number of edges) paths from `from` to all the verticles reachable from it
+    pub fn bfs_tree<'gr>(&'gr self, from: VertHandle<'gr, VD, EL>) -> BfsTree<'gr, VD, EL>
+    { self.multi_source_bfs([from]) }
+
+    /// Forest of shortest (by number of edges) paths from the nearest of `sources`
+    /// to all the verticles reachable from any of them
+    pub fn multi_source_bfs<'gr, It>(&'gr self, sources: It) -> BfsTree<'gr, VD, EL>
+        where It: IntoIterator<Item = VertHandle<'gr, VD, EL>>,
+    {
+        let mut nodes = vec![None; self.verts_cnt()];
+        let mut roots = vec![];
+        let mut queue = VecDeque::new();
+
+        sources.into_iter()
+            .for_each(|source| {
+                assert!(source.is_of(self), "source is from  different owner");
+                if nodes[source.id()].is_none() {
+                    nodes[source.id()] = Some(BfsNode{ depth: 0, parent: None, source: source.id() });
+                    roots.push(source);
+                    queue.push_back(source);
+                }
+            });
+
+        while let Some(from) = queue.pop_front() {
+            let BfsNode{ depth, source, .. } = nodes[from.id()]
+                .expect("enqueued verticles are reached");
+            from.edges()
+                .enumerate()
+                .for_each(|(edge_idx, (_, to))| {
+                    if nodes[to.id()].is_none() {
+                        nodes[to.id()] = Some(BfsNode{ depth: depth + 1, parent: Some((from.id(), edge_idx)), source });
+                        queue.push_back(to);
+                    }
+                });
+        }
+
+        BfsTree{ graph: self, roots, nodes }
+    }
+}
+
+impl<'gr, VD, EL> Iterator for BFSStepIterator<'gr, VD, EL>
+{
+    type Item = BfsStep<'gr, VD, EL>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        if let Some(step) = self.queue.pop_front() {
+            if self.max_depth.is_some_and(|max_depth| step.depth >= max_depth) {
+                return Some(step)
+            }
+            let children = step.vert.edges()
+                .enumerate()
+                .filter(|(_, (_, to))| !std::mem::replace(&mut self.marked[to.id()], true))
+                .map(|(edge_idx, (_, to))| BfsStep {
+                    vert: to,
+                    depth: step.depth + 1,
+                    parent: Some((step.vert, edge_idx)),
+                });
+            self.queue.extend(children);
+            Some(step)
+        } else {
+            if self.max_depth.is_some() {
+                return None
+            }
+            // the same search of unvisited component as in BFSIterator
+            let fst = self.last_root.map(|id| id + 1)
+                .unwrap_or(0);
+
+            let root = (fst..self.graph.verts_cnt())
+                .find(|id| !self.marked[*id])?;
+            self.last_root = Some(root);
+            self.marked[root] = true;
+
+            let vert = self.graph.get_vert(root)?;
+            let root = BfsStep{ vert, depth: 0, parent: None };
+            // root's children are enqueued as for any other verticle
+            self.queue.push_back(root);
+            self.next()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BfsNode
+{
+    depth: usize,
+    // (parent, edge_idx)
+    parent: Option<(usize, usize)>,
+    source: usize,
+}
+
+/// Result of [Graph::bfs_tree] and [Graph::multi_source_bfs]
+pub struct BfsTree<'gr, VD, EL>
+{
+    graph: &'gr Graph<VD, EL>,
+    roots: Vec<VertHandle<'gr, VD, EL>>,
+    nodes: Vec<Option<BfsNode>>,
+}
+
+impl<'gr, VD, EL> BfsTree<'gr, VD, EL>
+{
+    /// Sources of the search without duplicates
+    pub fn roots(&self) -> &[VertHandle<'gr, VD, EL>]
+    { &self.roots }
+
+    pub fn is_reachable(&self, id: usize) -> bool
+    { self.node(id).is_some() }
+
+    /// Number of edges on the shortest path from the nearest root, `None` if it's unreachable
+    pub fn depth(&self, id: usize) -> Option<usize>
+    { self.node(id).map(|node| node.depth) }
+
+    /// The nearest root, `None` if it's unreachable
+    pub fn source_of(&self, id: usize) -> Option<VertHandle<'gr, VD, EL>>
+    {
+        self.node(id)
+            .and_then(|node| self.graph.get_vert(node.source))
+    }
+
+    /// Verticle preceding `id` on the shortest path from the nearest root
+    pub fn parent(&self, id: usize) -> Option<VertHandle<'gr, VD, EL>>
+    {
+        self.node(id)?
+            .parent
+            .and_then(|(parent, _)| self.graph.get_vert(parent))
+    }
+
+    /// Shortest path from the nearest root to `id`, `None` if it's unreachable
+    pub fn path_to(&self, id: usize) -> Option<Path<'gr, VD, EL>>
+    {
+        self.node(id)?;
+        Some(Path::from_parents(self.graph, id, |curr| self.nodes[curr]
+            .and_then(|node| node.parent)))
+    }
+
+// private:
+    fn node(&self, id: usize) -> Option<BfsNode>
+    { self.nodes.get(id).copied().flatten() }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::test_util::{months, find};
+
+    #[test]
+    fn steps()
+    {
+        let graph = months();
+        let september = find(&graph, "September");
+
+        let steps: Vec<(String, usize, Option<String>)> = graph.bfs_steps_from(september)
+            .map(|step| (
+                step.vert().to_string(),
+                step.depth(),
+                step.label().cloned()))
+            .collect();
+
+        let expected = [
+            ("September", 0, None),
+            ("December", 1, Some("")),
+            ("June", 1, Some("")),
+            ("January", 2, Some("Happy New Year!")),
+            ("April", 2, Some("April Fools Day")),
+            ("March", 3, Some("")),
+            // the next component
+            ("May", 0, None),
+        ];
+        assert_eq!(steps.len(), expected.len());
+        steps.iter()
+            .zip(expected)
+            .for_each(|((month, depth, label), (exp_month, exp_depth, exp_label))| {
+                assert_eq!(month, exp_month);
+                assert_eq!(*depth, exp_depth);
+                assert_eq!(label.as_deref(), exp_label);
+            });
+    }
+
+    #[test]
+    fn within()
+    {
+        let graph = months();
+        let september = find(&graph, "September");
+
+        let months = |max_depth| graph.bfs_steps_within(september, max_depth)
+            .map(|step| step.vert().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(months(0), ["September"]);
+        assert_eq!(months(1), ["September", "December", "June"]);
+        assert_eq!(months(2), ["September", "December", "June", "January", "April"]);
+        // May is never reached
+        assert_eq!(months(10).len(), 6);
+    }
+
+    #[test]
+    fn visits_like_bfs()
+    {
+        let graph = months();
+        assert!(graph.bfs_steps()
+            .map(|step| step.vert().id())
+            .eq(graph.bfs().map(|vert| vert.id())));
+    }
+
+    #[test]
+    fn shortest_path()
+    {
+        let graph = months();
+        let tree = graph.bfs_tree(find(&graph, "September"));
+
+        let march = find(&graph, "March");
+        let path = tree.path_to(march.id())
+            .expect("March is reachable");
+        let months: Vec<String> = path.verts()
+            .iter()
+            .map(|vert| vert.to_string())
+            .collect();
+        assert_eq!(months, ["September", "December", "January", "March"]);
+        assert_eq!(path.labels(), [&"", &"Happy New Year!", &""]);
+        assert_eq!(tree.depth(march.id()), Some(3));
+        assert_eq!(tree.parent(march.id()).map(|v| v.id()), Some(find(&graph, "January").id()));
+
+        let may = find(&graph, "May");
+        assert!(!tree.is_reachable(may.id()));
+        assert!(tree.path_to(may.id()).is_none());
+        assert_eq!(tree.depth(may.id()), None);
+
+        let root_path = tree.path_to(tree.roots()[0].id())
+            .expect("root is reachable");
+        assert!(root_path.is_empty());
+        assert_eq!(root_path.source().id(), root_path.target().id());
+    }
+
+    #[test]
+    fn nearest_sources()
+    {
+        let graph = months();
+        let sources = ["June", "December", "May", "June"].map(|month| find(&graph, month));
+        let tree = graph.multi_source_bfs(sources);
+
+        let roots: Vec<usize> = tree.roots()
+            .iter()
+            .map(|root| root.id())
+            .collect();
+        assert_eq!(roots, sources[..3].iter().map(|v| v.id()).collect::<Vec<_>>());
+
+        let nearest = |month| {
+            let id = find(&graph, month).id();
+            tree.source_of(id)
+                .map(|source| (source.to_string(), tree.depth(id).expect("reachable")))
+        };
+        assert_eq!(nearest("June"), Some(("June".into(), 0)));
+        // June comes first among sources, so it wins ties
+        assert_eq!(nearest("January"), Some(("June".into(), 1)));
+        assert_eq!(nearest("April"), Some(("June".into(), 1)));
+        assert_eq!(nearest("March"), Some(("June".into(), 2)));
+        assert_eq!(nearest("December"), Some(("December".into(), 0)));
+        assert_eq!(nearest("September"), None);
+
+        let path = tree.path_to(find(&graph, "March").id())
+            .expect("March is reachable");
+        assert_eq!(path.source().id(), sources[0].id());
+        assert_eq!(path.len(), 2);
+    }
+}
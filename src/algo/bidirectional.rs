@@ -0,0 +1,171 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{Graph, Path, VertHandle};
+
+/// Index of incoming edges, which makes point-to-point searches from both ends possible.
+///
+/// Building it takes one pass over the graph, then every query only touches verticles
+/// around the shortest path. The index describes the graph at the moment it was built.
+pub struct BidirectionalBFS<'gr, VD, EL>
+{
+    graph: &'gr Graph<VD, EL>,
+    // (from, edge_idx) of edges coming to the verticle
+    in_edges: Vec<Vec<(usize, usize)>>,
+}
+
+// how a verticle was reached: (previous verticle, edge_idx) for the forward search,
+// or (next verticle, edge_idx of the current one) for the backward search
+type Reached = HashMap<usize, Option<(usize, usize)>>;
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Indexes the graph for [BidirectionalBFS] queries
+    pub fn bidirectional_bfs(&self) -> BidirectionalBFS<'_, VD, EL>
+    {
+        let mut in_edges = vec![vec![]; self.verts_cnt()];
+        self.verts()
+            .for_each(|from| from.edges()
+                .enumerate()
+                .for_each(|(edge_idx, (_, to))| in_edges[to.id()].push((from.id(), edge_idx))));
+        BidirectionalBFS{ graph: self, in_edges }
+    }
+}
+
+impl<'gr, VD, EL> BidirectionalBFS<'gr, VD, EL>
+{
+    /// Path from `from` to `to` with the least number of edges, `None` if `to` is unreachable
+    pub fn shortest_path(&self, from: VertHandle<'gr, VD, EL>, to: VertHandle<'gr, VD, EL>) -> Option<Path<'gr, VD, EL>>
+    {
+        assert!(from.is_of(self.graph) && to.is_of(self.graph), "verticles are from  different owner");
+
+        let mut forward = Reached::from([(from.id(), None)]);
+        let mut backward = Reached::from([(to.id(), None)]);
+        let mut forward_layer = VecDeque::from([from.id()]);
+        let mut backward_layer = VecDeque::from([to.id()]);
+
+        let meeting = when! {
+            from.id() == to.id() => from.id(),
+            _ => loop {
+                if forward_layer.is_empty() || backward_layer.is_empty() {
+                    return None
+                }
+                // expand the smaller side by the whole layer
+                let met = when! {
+                    forward_layer.len() <= backward_layer.len() =>
+                        self.expand_forward(&mut forward_layer, &mut forward, &backward),
+                    _ => self.expand_backward(&mut backward_layer, &mut backward, &forward),
+                };
+                if let Some(meeting) = met {
+                    break meeting
+                }
+            },
+        };
+
+        let mut path = Path::from_parents(self.graph, meeting, |curr| forward[&curr]);
+        let mut curr = meeting;
+        while let Some((next, edge_idx)) = backward[&curr] {
+            let (label, to) = path.target()
+                .edge(edge_idx)
+                .expect("path should go through valid edges");
+            debug_assert_eq!(to.id(), next);
+            path.push(label, to);
+            curr = next;
+        }
+        Some(path)
+    }
+
+    /// Least number of edges on a path from `from` to `to`, `None` if `to` is unreachable
+    pub fn distance(&self, from: VertHandle<'gr, VD, EL>, to: VertHandle<'gr, VD, EL>) -> Option<usize>
+    { self.shortest_path(from, to).map(|path| path.len()) }
+
+    /* Both searches check every new verticle against the other one's reached verticles.
+       Since the sides are expanded a whole layer at a time, the first met verticle
+       lies on a shortest path: any shorter one would have met the other side earlier
+    */
+    fn expand_forward(&self, layer: &mut VecDeque<usize>, reached: &mut Reached, other: &Reached) -> Option<usize>
+    {
+        for _ in 0..layer.len() {
+            let from = layer.pop_front()?;
+            let from_vert = self.graph.get_vert(from)?;
+            for (edge_idx, (_, to)) in from_vert.edges().enumerate() {
+                if reached.contains_key(&to.id()) {
+                    continue
+                }
+                reached.insert(to.id(), Some((from, edge_idx)));
+                if other.contains_key(&to.id()) {
+                    return Some(to.id())
+                }
+                layer.push_back(to.id());
+            }
+        }
+        None
+    }
+
+    fn expand_backward(&self, layer: &mut VecDeque<usize>, reached: &mut Reached, other: &Reached) -> Option<usize>
+    {
+        for _ in 0..layer.len() {
+            let to = layer.pop_front()?;
+            for &(from, edge_idx) in &self.in_edges[to] {
+                if reached.contains_key(&from) {
+                    continue
+                }
+                reached.insert(from, Some((to, edge_idx)));
+                if other.contains_key(&from) {
+                    return Some(from)
+                }
+                layer.push_back(from);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::test_util::graph_with;
+
+    use super::*;
+
+    #[test]
+    fn matches_bfs_tree()
+    {
+        // a grid-like graph with shortcuts and dead ends
+        let edges: Vec<(usize, usize)> = (0..30)
+            .flat_map(|id| [(id, (id + 1) % 30), (id, (id * 7 + 3) % 30)])
+            .chain([(30, 0), (5, 31)])
+            .collect();
+        let graph = graph_with(32, &edges);
+        let search = graph.bidirectional_bfs();
+
+        graph.verts()
+            .for_each(|from| {
+                let tree = graph.bfs_tree(from);
+                graph.verts()
+                    .for_each(|to| {
+                        let path = search.shortest_path(from, to);
+                        assert_eq!(path.as_ref().map(Path::len), tree.depth(to.id()), "from {} to {}", from.id(), to.id());
+
+                        if let Some(path) = path {
+                            assert_eq!(path.source().id(), from.id());
+                            assert_eq!(path.target().id(), to.id());
+                            path.edges()
+                                .for_each(|(from, label, to)| assert_eq!(edges[*label], (from.id(), to.id())));
+                        }
+                    });
+            });
+    }
+
+    #[test]
+    fn unreachable()
+    {
+        let graph = graph_with(4, &[(0, 1), (2, 3), (3, 2)]);
+        let search = graph.bidirectional_bfs();
+        let vert = |id| graph.get_vert(id).expect("vert exists");
+
+        assert_eq!(search.distance(vert(0), vert(1)), Some(1));
+        assert_eq!(search.distance(vert(1), vert(0)), None);
+        assert_eq!(search.distance(vert(0), vert(3)), None);
+        assert_eq!(search.distance(vert(3), vert(3)), Some(0));
+    }
+}
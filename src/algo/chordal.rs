@@ -0,0 +1,439 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::Graph;
+
+/* All the methods here see the graph as undirected: an edge in either direction
+   makes verticles adjacent, loops and repeated edges are ignored.
+   Orderings are lists of verticle ids, elimination orderings go from the first
+   eliminated verticle, i.e. they are reversed search orderings
+*/
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Lexicographic breadth-first search ordering of the undirected view.
+    /// Ties are broken by the least id, so the ordering starts from 0
+    pub fn lex_bfs(&self) -> Vec<usize>
+    {
+        let adjacent = self.undirected_adjacency();
+        let mut visited = vec![false; adjacent.len()];
+        let mut partition = Partition::new(adjacent.len());
+        let mut order = Vec::with_capacity(adjacent.len());
+
+        while let Some(vert) = partition.pop_first() {
+            visited[vert] = true;
+            order.push(vert);
+
+            // neighbours get greater labels than the rest of their class
+            adjacent[vert].iter()
+                .filter(|to| !visited[**to])
+                .for_each(|to| partition.split_off(*to));
+            partition.finish_splits();
+        }
+        order
+    }
+
+    /// Maximum cardinality search ordering of the undirected view: the next verticle
+    /// has the most visited neighbours, ties are broken by the least id
+    pub fn max_cardinality_search(&self) -> Vec<usize>
+    {
+        let adjacent = self.undirected_adjacency();
+        let mut visited = vec![false; adjacent.len()];
+        let mut cardinality = vec![0; adjacent.len()];
+        // verticles by cardinality with the least id on top,
+        // outdated entries are dropped when they get on top
+        let mut buckets: Vec<BinaryHeap<Reverse<usize>>> = vec![(0..adjacent.len()).map(Reverse).collect()];
+        let mut level = 0;
+        let mut order = Vec::with_capacity(adjacent.len());
+
+        loop {
+            let vert = match buckets[level].pop() {
+                Some(Reverse(vert)) if !visited[vert] && cardinality[vert] == level => vert,
+                Some(_) => continue,
+                None if level == 0 => break,
+                None => {
+                    level -= 1;
+                    continue
+                },
+            };
+            visited[vert] = true;
+            order.push(vert);
+
+            adjacent[vert].iter()
+                .filter(|to| !visited[**to])
+                .for_each(|to| {
+                    cardinality[*to] += 1;
+                    if buckets.len() <= cardinality[*to] {
+                        buckets.push(BinaryHeap::new());
+                    }
+                    buckets[cardinality[*to]].push(Reverse(*to));
+                    level = level.max(cardinality[*to]);
+                });
+        }
+        order
+    }
+
+    /// Whether neighbours of every verticle, which come later in `order`, form a clique.
+    /// `order` should list every verticle exactly once
+    pub fn is_perfect_elimination_ordering(&self, order: &[usize]) -> bool
+    {
+        let adjacent = self.undirected_adjacency();
+        let Some(position) = positions(order, adjacent.len()) else {
+            return false
+        };
+
+        // it's enough to check that later neighbours of every verticle are adjacent
+        // to the earliest of them, see Tarjan & Yannakakis, 1984
+        order.iter()
+            .all(|vert| {
+                let later = later_neighbours(&adjacent[*vert], &position, position[*vert]);
+                let Some(parent) = later.iter().min_by_key(|to| position[**to]) else {
+                    return true
+                };
+                later.iter()
+                    .filter(|to| *to != parent)
+                    .all(|to| adjacent[*parent].binary_search(to).is_ok())
+            })
+    }
+
+    /// Whether the undirected view has no chordless cycles longer than 3
+    pub fn is_chordal(&self) -> bool
+    { self.chordal_elimination_ordering().is_some() }
+
+    /// Perfect elimination ordering if the undirected view is chordal
+    pub fn chordal_elimination_ordering(&self) -> Option<Vec<usize>>
+    {
+        let mut order = self.max_cardinality_search();
+        order.reverse();
+        when! {
+            self.is_perfect_elimination_ordering(&order) => Some(order),
+            _ => None,
+        }
+    }
+
+    /// All the maximal cliques of the undirected view if it's chordal,
+    /// there are at most as many of them as verticles. Cliques are listed in the order of
+    /// [Graph::chordal_elimination_ordering] by their earliest verticle, every clique is sorted by id
+    pub fn maximal_cliques(&self) -> Option<Vec<Vec<usize>>>
+    {
+        let order = self.chordal_elimination_ordering()?;
+        let adjacent = self.undirected_adjacency();
+        let position = positions(&order, adjacent.len())
+            .expect("ordering is a permutation");
+
+        let later: Vec<Vec<usize>> = order.iter()
+            .map(|vert| later_neighbours(&adjacent[*vert], &position, position[*vert]))
+            .collect();
+
+        // a verticle with its later neighbours is a clique, which is not maximal
+        // only if it's a part of the clique of the earlier verticle it's the parent of
+        let mut covered = vec![false; order.len()];
+        later.iter()
+            .for_each(|later_of_vert| {
+                let parent = later_of_vert.iter()
+                    .min_by_key(|to| position[**to]);
+                if let Some(parent) = parent {
+                    let parent_pos = position[*parent];
+                    if later[parent_pos].len() + 1 == later_of_vert.len() {
+                        covered[parent_pos] = true;
+                    }
+                }
+            });
+
+        let cliques = order.iter()
+            .zip(later)
+            .zip(covered)
+            .filter(|(_, covered)| !covered)
+            .map(|((vert, mut clique), _)| {
+                clique.push(*vert);
+                clique.sort_unstable();
+                clique
+            })
+            .collect();
+        Some(cliques)
+    }
+
+// private:
+    /// Sorted lists of neighbours in the undirected view
+    pub (crate) fn undirected_adjacency(&self) -> Vec<Vec<usize>>
+    {
+        let mut adjacent = vec![vec![]; self.verts_cnt()];
+        self.verts()
+            .for_each(|from| from.edges()
+                .filter(|(_, to)| to.id() != from.id())
+                .for_each(|(_, to)| {
+                    adjacent[from.id()].push(to.id());
+                    adjacent[to.id()].push(from.id());
+                }));
+        adjacent.iter_mut()
+            .for_each(|neighbours: &mut Vec<usize>| {
+                neighbours.sort_unstable();
+                neighbours.dedup();
+            });
+        adjacent
+    }
+}
+
+/// Position of every verticle in `order`, `None` if it's not a permutation of `0..cnt`
+fn positions(order: &[usize], cnt: usize) -> Option<Vec<usize>>
+{
+    if order.len() != cnt {
+        return None
+    }
+    let mut position = vec![usize::MAX; cnt];
+    for (pos, vert) in order.iter().enumerate() {
+        if *position.get(*vert)? != usize::MAX {
+            return None
+        }
+        position[*vert] = pos;
+    }
+    Some(position)
+}
+
+fn later_neighbours(neighbours: &[usize], position: &[usize], pos: usize) -> Vec<usize>
+{
+    neighbours.iter()
+        .filter(|to| position[**to] > pos)
+        .copied()
+        .collect()
+}
+
+/// Ordered partition of verticles for [Graph::lex_bfs]. Classes make a linked list from the greatest label,
+/// verticles of every class make a linked list sorted by id
+struct Partition
+{
+    classes: Vec<Class>,
+    first: Option<usize>,
+    class_of: Vec<usize>,
+    prev: Vec<Option<usize>>,
+    next: Vec<Option<usize>>,
+    // classes split by the last pivot
+    split: Vec<usize>,
+}
+
+#[derive(Default)]
+struct Class
+{
+    head: Option<usize>,
+    tail: Option<usize>,
+    prev: Option<usize>,
+    next: Option<usize>,
+    // class of the verticles split off by the current pivot, it goes right before this one
+    split_off: Option<usize>,
+}
+
+impl Partition
+{
+    fn new(cnt: usize) -> Self
+    {
+        let mut partition = Partition {
+            classes: vec![],
+            first: None,
+            class_of: vec![0; cnt],
+            prev: vec![None; cnt],
+            next: vec![None; cnt],
+            split: vec![],
+        };
+        if cnt > 0 {
+            let class = partition.insert_class_before(None);
+            (0..cnt).for_each(|vert| partition.push(class, vert));
+        }
+        partition
+    }
+
+    /// Removes the least verticle of the first class
+    fn pop_first(&mut self) -> Option<usize>
+    {
+        let class = self.first?;
+        let vert = self.classes[class].head
+            .expect("classes are never empty");
+        self.unlink(vert);
+        self.drop_if_empty(class);
+        Some(vert)
+    }
+
+    /// Moves `vert` to the class right before its own one. Verticles have to come
+    /// by ascending ids to keep classes sorted
+    fn split_off(&mut self, vert: usize)
+    {
+        let class = self.class_of[vert];
+        let new_class = match self.classes[class].split_off {
+            Some(new_class) => new_class,
+            None => {
+                let new_class = self.insert_class_before(Some(class));
+                self.classes[class].split_off = Some(new_class);
+                self.split.push(class);
+                new_class
+            },
+        };
+        self.unlink(vert);
+        self.push(new_class, vert);
+    }
+
+    /// Forgets about classes split by the pivot, which are dropped if they got empty
+    fn finish_splits(&mut self)
+    {
+        while let Some(class) = self.split.pop() {
+            self.classes[class].split_off = None;
+            self.drop_if_empty(class);
+        }
+    }
+
+    fn insert_class_before(&mut self, before: Option<usize>) -> usize
+    {
+        let class = self.classes.len();
+        let prev = match before {
+            Some(before) => self.classes[before].prev,
+            None => None,
+        };
+        self.classes.push(Class{ prev, next: before, ..Class::default() });
+        match prev {
+            Some(prev) => self.classes[prev].next = Some(class),
+            None => self.first = Some(class),
+        }
+        if let Some(before) = before {
+            self.classes[before].prev = Some(class);
+        }
+        class
+    }
+
+    fn drop_if_empty(&mut self, class: usize)
+    {
+        let Class{ head, prev, next, .. } = self.classes[class];
+        if head.is_some() {
+            return
+        }
+        match prev {
+            Some(prev) => self.classes[prev].next = next,
+            None => self.first = next,
+        }
+        if let Some(next) = next {
+            self.classes[next].prev = prev;
+        }
+    }
+
+    fn push(&mut self, class: usize, vert: usize)
+    {
+        self.class_of[vert] = class;
+        self.prev[vert] = self.classes[class].tail;
+        self.next[vert] = None;
+        match self.classes[class].tail {
+            Some(tail) => self.next[tail] = Some(vert),
+            None => self.classes[class].head = Some(vert),
+        }
+        self.classes[class].tail = Some(vert);
+    }
+
+    fn unlink(&mut self, vert: usize)
+    {
+        let class = self.class_of[vert];
+        match self.prev[vert] {
+            Some(prev) => self.next[prev] = self.next[vert],
+            None => self.classes[class].head = self.next[vert],
+        }
+        match self.next[vert] {
+            Some(next) => self.prev[next] = self.prev[vert],
+            None => self.classes[class].tail = self.prev[vert],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::test_util::graph_with;
+
+    use super::*;
+
+    fn is_permutation(order: &[usize], cnt: usize) -> bool
+    { positions(order, cnt).is_some() }
+
+    #[test]
+    fn orderings()
+    {
+        // path 0 - 1 - 2 - 3 with 4 connected to 0 and 2, edges go both ways
+        let graph = graph_with(5, &[(1, 0), (1, 2), (3, 2), (0, 4), (4, 2), (2, 4)]);
+
+        let lex = graph.lex_bfs();
+        // 1 goes before 4 by id, but 4 was labelled by 0 earlier than 2 was labelled by 1
+        assert_eq!(lex, [0, 1, 4, 2, 3]);
+
+        let mcs = graph.max_cardinality_search();
+        assert!(is_permutation(&mcs, 5));
+        assert_eq!(mcs[..2], [0, 1]);
+        // 2 and 4 both have one visited neighbour, the least id wins
+        assert_eq!(mcs[2], 2);
+    }
+
+    #[test]
+    fn chordal()
+    {
+        // two triangles sharing the edge 1 - 2 and a pendant verticle 4
+        let graph = graph_with(5, &[(0, 1), (1, 2), (2, 0), (1, 3), (3, 2), (3, 4), (4, 4)]);
+        assert!(graph.is_chordal());
+
+        let order = graph.chordal_elimination_ordering()
+            .expect("graph is chordal");
+        assert!(graph.is_perfect_elimination_ordering(&order));
+        let mut lex = graph.lex_bfs();
+        lex.reverse();
+        assert!(graph.is_perfect_elimination_ordering(&lex));
+
+        let mut cliques = graph.maximal_cliques()
+            .expect("graph is chordal");
+        cliques.sort();
+        assert_eq!(cliques, [vec![0, 1, 2], vec![1, 2, 3], vec![3, 4]]);
+
+        // 1 is eliminated first, but 0 and 3 aren't adjacent
+        assert!(!graph.is_perfect_elimination_ordering(&[1, 0, 2, 3, 4]));
+        assert!(!graph.is_perfect_elimination_ordering(&[0, 1, 2, 3]));
+        assert!(!graph.is_perfect_elimination_ordering(&[0, 1, 2, 3, 3]));
+    }
+
+    #[test]
+    fn not_chordal()
+    {
+        // chordless square
+        let graph = graph_with(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        assert!(!graph.is_chordal());
+        assert!(graph.maximal_cliques().is_none());
+
+        // a chord fixes it
+        let mut graph = graph;
+        graph.add_edge(2, 0, 0)
+            .expect("verts exist");
+        let mut cliques = graph.maximal_cliques()
+            .expect("graph is chordal");
+        cliques.sort();
+        assert_eq!(cliques, [vec![0, 1, 2], vec![0, 2, 3]]);
+    }
+
+    #[test]
+    fn isolated_and_empty()
+    {
+        let graph = graph_with(3, &[]);
+        assert_eq!(graph.lex_bfs(), [0, 1, 2]);
+        assert_eq!(graph.max_cardinality_search(), [0, 1, 2]);
+        // cliques follow the elimination ordering, i.e. reversed search
+        assert_eq!(graph.maximal_cliques(), Some(vec![vec![2], vec![1], vec![0]]));
+
+        let graph = graph_with(0, &[]);
+        assert!(graph.is_chordal());
+        assert_eq!(graph.maximal_cliques(), Some(vec![]));
+    }
+
+    #[test]
+    fn long_path_and_star()
+    {
+        // both searches are linear, so they would take ages on such graphs otherwise
+        const SIZE: usize = 100_000;
+        let path: Vec<(usize, usize)> = (1..SIZE).map(|id| (id - 1, id)).collect();
+        let graph = graph_with(SIZE, &path);
+        let straight: Vec<usize> = (0..SIZE).collect();
+        assert_eq!(graph.lex_bfs(), straight);
+        assert_eq!(graph.max_cardinality_search(), straight);
+
+        let star: Vec<(usize, usize)> = (1..SIZE).map(|id| (0, id)).collect();
+        let graph = graph_with(SIZE, &star);
+        assert_eq!(graph.lex_bfs(), straight);
+        assert_eq!(graph.max_cardinality_search(), straight);
+    }
+}
@@ -0,0 +1,70 @@
+/// Partition of verticles into components.
+///
+/// Components are numbered in order of their first members in [Graph::verts](crate::Graph::verts),
+/// so the component of verticle 0 is always 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components
+{
+    component_of: Vec<usize>,
+    members: Vec<Vec<usize>>,
+}
+
+impl Components
+{
+    /// Renumbers arbitrary labels of verticles by their first appearance
+    pub (crate) fn from_labels(labels: &[usize]) -> Self
+    {
+        let mut renumbered = vec![usize::MAX; labels.iter().max().map_or(0, |max| max + 1)];
+        let mut members: Vec<Vec<usize>> = vec![];
+
+        let component_of = labels.iter()
+            .enumerate()
+            .map(|(vert, label)| {
+                if renumbered[*label] == usize::MAX {
+                    renumbered[*label] = members.len();
+                    members.push(vec![]);
+                }
+                members[renumbered[*label]].push(vert);
+                renumbered[*label]
+            })
+            .collect();
+        Components{ component_of, members }
+    }
+
+    /// Number of components
+    pub fn count(&self) -> usize
+    { self.members.len() }
+
+    pub fn component_of(&self, vert_id: usize) -> Option<usize>
+    { self.component_of.get(vert_id).copied() }
+
+    /// Ids of verticles in the component sorted in ascending order
+    pub fn members(&self, component: usize) -> Option<&[usize]>
+    { self.members.get(component).map(Vec::as_slice) }
+
+    /// Number of verticles in the component
+    pub fn size(&self, component: usize) -> Option<usize>
+    { self.members(component).map(<[usize]>::len) }
+
+    /// Numbers of verticles in all the components in order of their ids
+    pub fn sizes(&self) -> Vec<usize>
+    { self.members.iter().map(Vec::len).collect() }
+
+    /// Component with the most verticles, the first one if there are several of them
+    pub fn largest(&self) -> Option<usize>
+    {
+        self.members.iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, members)| members.len())
+            .map(|(component, _)| component)
+    }
+
+    /// All the components in order of their ids
+    pub fn iter(&self) -> impl Iterator<Item = &[usize]>
+    { self.members.iter().map(Vec::as_slice) }
+
+    /// Whether both verticles are in the same component
+    pub fn same(&self, fst: usize, snd: usize) -> bool
+    { self.component_of(fst).is_some_and(|component| self.component_of(snd) == Some(component)) }
+}
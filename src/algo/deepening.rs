@@ -0,0 +1,220 @@
+use crate::{EdgeIter, Graph, Path, VertHandle};
+
+/// Depth-first traversal of verticles within a number of edges from the root, see [Graph::dfs_within]
+pub struct DepthLimitedDFS<'gr, VD, EL>
+{
+    max_depth: usize,
+    // the least depth a verticle was reached at
+    best_depth: Vec<usize>,
+    reported: Vec<bool>,
+    stack: PathStack<'gr, VD, EL>,
+    pending_root: bool,
+    // verticles, which were unreached when edges to them weren't followed because of the limit
+    cut_off: Vec<usize>,
+}
+
+// current path: verticles with their remaining edges and labels of the edges leading to them
+type PathStack<'gr, VD, EL> = Vec<(VertHandle<'gr, VD, EL>, EdgeIter<'gr, VD, EL>, Option<&'gr EL>)>;
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Depth-first traversal yielding every verticle within `max_depth` edges from `from`
+    /// once, along with its depth on the path it was first reached by. That path isn't
+    /// necessarily the shortest one, see [Graph::bfs_steps_within] for shortest depths.
+    ///
+    /// Verticles reached by a shorter path later are explored again,
+    /// so nothing within the limit is missed
+    pub fn dfs_within<'gr>(&'gr self, from: VertHandle<'gr, VD, EL>, max_depth: usize) -> DepthLimitedDFS<'gr, VD, EL>
+    {
+        assert!(from.is_of(self), "'from' is from  different owner");
+
+        let mut best_depth = vec![usize::MAX; self.verts_cnt()];
+        best_depth[from.id()] = 0;
+        let mut reported = vec![false; self.verts_cnt()];
+        reported[from.id()] = true;
+
+        DepthLimitedDFS {
+            max_depth,
+            best_depth,
+            reported,
+            stack: vec![(from, from.edges(), None)],
+            pending_root: true,
+            cut_off: vec![],
+        }
+    }
+
+    /// Iterative-deepening search of the nearest verticle, which data matches `is_target`,
+    /// among ones within `max_depth` edges from `from`.
+    /// Returns the shortest path to it, `None` if there is no such verticle.
+    ///
+    /// Unlike BFS it doesn't keep a queue of the search frontier, only the current path,
+    /// but it still keeps a few marks for every verticle of the graph
+    pub fn iddfs<'gr, F>(&'gr self, from: VertHandle<'gr, VD, EL>, max_depth: usize, mut is_target: F) -> Option<Path<'gr, VD, EL>>
+        where F: FnMut(&VD) -> bool,
+    {
+        for depth in 0..=max_depth {
+            let mut dfs = self.dfs_within(from, depth);
+            // every verticle nearer than 'depth' was checked on the previous iterations
+            while let Some((vert, _)) = dfs.next() {
+                if is_target(&**vert) {
+                    return Some(dfs.path())
+                }
+            }
+            if !dfs.is_cut_off() {
+                // everything reachable is within the limit already
+                return None
+            }
+        }
+        None
+    }
+}
+
+impl<'gr, VD, EL> DepthLimitedDFS<'gr, VD, EL>
+{
+    /// Whether some verticles weren't reached because of the limit
+    fn is_cut_off(&self) -> bool
+    {
+        self.cut_off.iter()
+            .any(|id| self.best_depth[*id] == usize::MAX)
+    }
+
+    /// Path from the root to the last yielded verticle
+    fn path(&self) -> Path<'gr, VD, EL>
+    {
+        let (root, _, _) = self.stack[0];
+        self.stack[1..].iter()
+            .fold(Path::new(root), |mut path, (vert, _, label)| {
+                path.push(label.expect("only the root has no label"), *vert);
+                path
+            })
+    }
+}
+
+impl<'gr, VD, EL> Iterator for DepthLimitedDFS<'gr, VD, EL>
+{
+    /// Verticle and its depth
+    type Item = (VertHandle<'gr, VD, EL>, usize);
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        if std::mem::take(&mut self.pending_root) {
+            return self.stack.first()
+                .map(|(root, _, _)| (*root, 0))
+        }
+
+        loop {
+            let depth = self.stack.len() - 1;
+            let (_, edges, _) = self.stack.last_mut()?;
+            let Some((label, to)) = edges.next() else {
+                self.stack.pop();
+                if self.stack.is_empty() {
+                    return None
+                }
+                continue
+            };
+
+            if depth == self.max_depth {
+                if self.best_depth[to.id()] == usize::MAX {
+                    self.cut_off.push(to.id());
+                }
+                continue
+            }
+            if self.best_depth[to.id()] <= depth + 1 {
+                continue
+            }
+            self.best_depth[to.id()] = depth + 1;
+            self.stack.push((to, to.edges(), Some(label)));
+
+            if !std::mem::replace(&mut self.reported[to.id()], true) {
+                return Some((to, depth + 1))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::test_util::{months, find};
+
+    use super::*;
+
+    #[test]
+    fn within()
+    {
+        let graph = months();
+        let september = find(&graph, "September");
+
+        let months = |max_depth| {
+            let mut months: Vec<String> = graph.dfs_within(september, max_depth)
+                .map(|(vert, _)| vert.to_string())
+                .collect();
+            months.sort();
+            months
+        };
+        assert_eq!(months(0), ["September"]);
+        assert_eq!(months(1), ["December", "June", "September"]);
+        assert_eq!(months(2), ["April", "December", "January", "June", "September"]);
+
+        // the same verticles as found by the limited BFS
+        (0..5).for_each(|max_depth| {
+            let mut bfs: Vec<String> = graph.bfs_steps_within(september, max_depth)
+                .map(|step| step.vert().to_string())
+                .collect();
+            bfs.sort();
+            assert_eq!(months(max_depth), bfs);
+        });
+    }
+
+    #[test]
+    fn shorter_path_found_later()
+    {
+        // 0 -> 1 -> 2 -> 3 is explored first, then 0 -> 2 makes 3 reachable within 2 edges
+        let mut graph = Graph::<usize, ()>::new();
+        graph.extend(0..4);
+        graph.add_edge(0, 1, ())
+            .and_then(|g| g.add_edge(1, 2, ()))
+            .and_then(|g| g.add_edge(2, 3, ()))
+            .and_then(|g| g.add_edge(0, 2, ()))
+            .expect("verts exist");
+        let root = graph.get_vert(0).expect("vert exists");
+
+        let found: Vec<(usize, usize)> = graph.dfs_within(root, 2)
+            .map(|(vert, depth)| (vert.id(), depth))
+            .collect();
+        assert_eq!(found, [(0, 0), (1, 1), (2, 2), (3, 2)]);
+
+        let path = graph.iddfs(root, 5, |data| *data == 3)
+            .expect("3 is reachable");
+        assert_eq!(path.ids(), [0, 2, 3]);
+    }
+
+    #[test]
+    fn iterative_deepening()
+    {
+        let graph = months();
+        let september = find(&graph, "September");
+
+        let path = graph.iddfs(september, 10, |month| month.starts_with('M'))
+            .expect("March is reachable");
+        let months: Vec<String> = path.verts()
+            .iter()
+            .map(|vert| vert.to_string())
+            .collect();
+        assert_eq!(months.len(), 4);
+        assert_eq!(months.last().map(String::as_str), Some("March"));
+
+        assert!(graph.iddfs(september, 2, |month| month == "March").is_none());
+        assert!(graph.iddfs(september, 0, |month| month == "September").is_some());
+        // May isn't reachable, the search stops before the limit
+        assert!(graph.iddfs(september, usize::MAX, |month| month == "May").is_none());
+
+        // even if there are cycles
+        let (march, september) = (find(&graph, "March").id(), september.id());
+        let mut graph = graph;
+        graph.add_edge(march, september, String::new())
+            .expect("verts exist");
+        let september = graph.get_vert(september).expect("vert exists");
+        assert!(graph.iddfs(september, usize::MAX, |month| month == "May").is_none());
+    }
+}
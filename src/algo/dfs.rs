@@ -0,0 +1,241 @@
+use crate::{EdgeIter, Graph, VertHandle};
+
+/// Step of depth-first traversal, see [Graph::dfs].
+///
+/// Every verticle is discovered and finished exactly once, every edge is reported once,
+/// right before the verticle it leads to is discovered if it's a tree edge.
+#[derive(Debug)]
+pub enum DfsEvent<'gr, VD, EL>
+{
+    Discover(VertHandle<'gr, VD, EL>),
+    /// Edge to an undiscovered verticle, which is discovered next
+    TreeEdge(VertHandle<'gr, VD, EL>, &'gr EL, VertHandle<'gr, VD, EL>),
+    /// Edge to a verticle, which is discovered, but not finished, i.e. closing a cycle
+    BackEdge(VertHandle<'gr, VD, EL>, &'gr EL, VertHandle<'gr, VD, EL>),
+    /// Edge to an already finished descendant
+    ForwardEdge(VertHandle<'gr, VD, EL>, &'gr EL, VertHandle<'gr, VD, EL>),
+    /// Edge to an already finished verticle, which is not a descendant
+    CrossEdge(VertHandle<'gr, VD, EL>, &'gr EL, VertHandle<'gr, VD, EL>),
+    /// All the edges of the verticle are reported
+    Finish(VertHandle<'gr, VD, EL>),
+}
+
+impl<VD, EL> Clone for DfsEvent<'_, VD, EL>
+{
+    fn clone(&self) -> Self
+    { *self }
+}
+
+impl<VD, EL> Copy for DfsEvent<'_, VD, EL> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State
+{
+    Undiscovered,
+    Discovered,
+    Finished,
+}
+
+pub struct DFSIterator<'gr, VD, EL>
+{
+    graph: &'gr Graph<VD, EL>,
+    state: Vec<State>,
+    discovered_at: Vec<usize>,
+    time: usize,
+    stack: Vec<(VertHandle<'gr, VD, EL>, EdgeIter<'gr, VD, EL>)>,
+    // discovery, which follows a tree edge
+    pending: Option<VertHandle<'gr, VD, EL>>,
+    last_root: Option<usize>,
+}
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Depth-first traversal of the whole graph. When all the verticles reachable from
+    /// the current root are finished, the next undiscovered one becomes a root, the same way
+    /// as in [Graph::bfs]. The traversal is iterative, so deep graphs don't overflow the stack
+    pub fn dfs(&self) -> DFSIterator<'_, VD, EL>
+    {
+        DFSIterator {
+            graph: self,
+            state: vec![State::Undiscovered; self.verts_cnt()],
+            discovered_at: vec![0; self.verts_cnt()],
+            time: 0,
+            stack: vec![],
+            pending: None,
+            last_root: None,
+        }
+    }
+
+    /// Same as [Graph::dfs], but `from` is the first root
+    pub fn dfs_from<'gr>(&'gr self, from: VertHandle<'gr, VD, EL>) -> DFSIterator<'gr, VD, EL>
+    {
+        assert!(from.is_of(self), "'from' is from  different owner");
+
+        let mut dfs = self.dfs();
+        dfs.pending = Some(from);
+        dfs
+    }
+}
+
+impl<'gr, VD, EL> DFSIterator<'gr, VD, EL>
+{
+    fn discover(&mut self, vert: VertHandle<'gr, VD, EL>) -> DfsEvent<'gr, VD, EL>
+    {
+        self.state[vert.id()] = State::Discovered;
+        self.discovered_at[vert.id()] = self.time;
+        self.time += 1;
+        self.stack.push((vert, vert.edges()));
+        DfsEvent::Discover(vert)
+    }
+
+    fn next_root(&mut self) -> Option<VertHandle<'gr, VD, EL>>
+    {
+        // there are no undiscovered verticles before 'last_root', see BFSIterator
+        let fst = self.last_root.map(|id| id + 1)
+            .unwrap_or(0);
+
+        let root = (fst..self.graph.verts_cnt())
+            .find(|id| self.state[*id] == State::Undiscovered)?;
+        self.last_root = Some(root);
+        self.graph.get_vert(root)
+    }
+}
+
+impl<'gr, VD, EL> Iterator for DFSIterator<'gr, VD, EL>
+{
+    type Item = DfsEvent<'gr, VD, EL>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        if let Some(vert) = self.pending.take() {
+            return Some(self.discover(vert))
+        }
+
+        let Some((from, edges)) = self.stack.last_mut() else {
+            let root = self.next_root()?;
+            return Some(self.discover(root))
+        };
+        let from = *from;
+
+        match edges.next() {
+            Some((label, to)) => Some(match self.state[to.id()] {
+                State::Undiscovered => {
+                    self.pending = Some(to);
+                    DfsEvent::TreeEdge(from, label, to)
+                },
+                State::Discovered => DfsEvent::BackEdge(from, label, to),
+                State::Finished => when! {
+                    self.discovered_at[from.id()] < self.discovered_at[to.id()] => DfsEvent::ForwardEdge(from, label, to),
+                    _ => DfsEvent::CrossEdge(from, label, to),
+                },
+            }),
+            None => {
+                self.stack.pop();
+                self.state[from.id()] = State::Finished;
+                Some(DfsEvent::Finish(from))
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::test_util::graph_with;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Ev
+    {
+        Discover(usize),
+        Tree(usize, usize),
+        Back(usize, usize),
+        Forward(usize, usize),
+        Cross(usize, usize),
+        Finish(usize),
+    }
+
+    fn ids(event: DfsEvent<'_, (), usize>) -> Ev
+    {
+        match event {
+            DfsEvent::Discover(v) => Ev::Discover(v.id()),
+            DfsEvent::TreeEdge(from, _, to) => Ev::Tree(from.id(), to.id()),
+            DfsEvent::BackEdge(from, _, to) => Ev::Back(from.id(), to.id()),
+            DfsEvent::ForwardEdge(from, _, to) => Ev::Forward(from.id(), to.id()),
+            DfsEvent::CrossEdge(from, _, to) => Ev::Cross(from.id(), to.id()),
+            DfsEvent::Finish(v) => Ev::Finish(v.id()),
+        }
+    }
+
+    #[test]
+    fn edge_classification()
+    {
+        // 0 -> 1 -> 2 -> 0 is a cycle, 0 -> 2 is forward, 3 -> 1 is cross
+        let graph = graph_with(5, &[(0, 1), (1, 2), (2, 0), (0, 2), (3, 1), (4, 4)]);
+
+        use Ev::*;
+        let events: Vec<_> = graph.dfs().map(ids).collect();
+        assert_eq!(events, [
+            Discover(0),
+            Tree(0, 1),
+            Discover(1),
+            Tree(1, 2),
+            Discover(2),
+            Back(2, 0),
+            Finish(2),
+            Finish(1),
+            Forward(0, 2),
+            Finish(0),
+            Discover(3),
+            Cross(3, 1),
+            Finish(3),
+            Discover(4),
+            Back(4, 4),
+            Finish(4),
+        ]);
+    }
+
+    #[test]
+    fn from_root()
+    {
+        let graph = graph_with(4, &[(2, 0), (0, 1), (3, 3)]);
+        let start = graph.get_vert(2).expect("vert exists");
+
+        use Ev::*;
+        let events: Vec<_> = graph.dfs_from(start).map(ids).collect();
+        assert_eq!(events, [
+            Discover(2),
+            Tree(2, 0),
+            Discover(0),
+            Tree(0, 1),
+            Discover(1),
+            Finish(1),
+            Finish(0),
+            Finish(2),
+            Discover(3),
+            Back(3, 3),
+            Finish(3),
+        ]);
+    }
+
+    #[test]
+    fn deep_chain()
+    {
+        const SIZE: usize = 100_000;
+        let mut graph = Graph::<(), ()>::new();
+        graph.extend(std::iter::repeat_n((), SIZE));
+        (1..SIZE).for_each(|id| {
+            graph.add_edge(id - 1, id, ())
+                .expect("verts exist");
+        });
+
+        let finished: Vec<usize> = graph.dfs()
+            .filter_map(|event| match event {
+                DfsEvent::Finish(v) => Some(v.id()),
+                _ => None,
+            })
+            .collect();
+        assert!(finished.into_iter().eq((0..SIZE).rev()));
+    }
+}
@@ -0,0 +1,306 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::{EdgeWeight, EdgeWeights, Graph, Path, VertHandle, Weight, WeightResult};
+
+/// Distances from a single source along with the tree of shortest paths,
+/// see [Graph::dijkstra]
+#[derive(Debug)]
+pub struct ShortestPaths<'gr, VD, EL, W>
+{
+    pub (crate) graph: &'gr Graph<VD, EL>,
+    pub (crate) source: usize,
+    pub (crate) distance: Vec<Option<W>>,
+    pub (crate) parent: Parents,
+}
+
+// (parent, edge_idx) of every verticle
+pub (crate) type Parents = Vec<Option<(usize, usize)>>;
+
+impl<'gr, VD, EL, W> ShortestPaths<'gr, VD, EL, W>
+    where W: Weight,
+{
+    pub (crate) fn new(graph: &'gr Graph<VD, EL>, source: usize) -> Self
+    {
+        let mut distance = vec![None; graph.verts_cnt()];
+        distance[source] = Some(W::zero());
+        ShortestPaths{ graph, source, distance, parent: vec![None; graph.verts_cnt()] }
+    }
+
+    /// Whether going to `to` through `edge_idx`-th edge of `from` is shorter than known path.
+    /// If it is, it becomes the known path
+    pub (crate) fn relax(&mut self, from: usize, edge_idx: usize, to: usize, weight: W) -> bool
+    {
+        let Some(through) = self.distance[from].map(|dist| dist + weight) else {
+            return false
+        };
+        if self.distance[to].is_some_and(|dist| dist <= through) {
+            return false
+        }
+        self.distance[to] = Some(through);
+        self.parent[to] = Some((from, edge_idx));
+        true
+    }
+
+    pub fn source(&self) -> VertHandle<'gr, VD, EL>
+    {
+        self.graph.get_vert(self.source)
+            .expect("source is a valid verticle")
+    }
+
+    /// Length of the shortest path to `id`, `None` if it's unreachable
+    pub fn distance(&self, id: usize) -> Option<W>
+    { self.distance.get(id).copied().flatten() }
+
+    pub fn is_reachable(&self, id: usize) -> bool
+    { self.distance(id).is_some() }
+
+    /// Verticle preceding `id` on the shortest path
+    pub fn parent(&self, id: usize) -> Option<VertHandle<'gr, VD, EL>>
+    {
+        self.parent.get(id)
+            .copied()
+            .flatten()
+            .and_then(|(parent, _)| self.graph.get_vert(parent))
+    }
+
+    /// Shortest path from the source to `id`, `None` if it's unreachable
+    pub fn path_to(&self, id: usize) -> Option<Path<'gr, VD, EL>>
+    {
+        self.distance(id)?;
+        Some(Path::from_parents(self.graph, id, |curr| self.parent[curr]))
+    }
+}
+
+/// Entry of a priority queue, which pops the least priority first
+pub (crate) struct MinFirst<W>
+{
+    pub (crate) priority: W,
+    pub (crate) vert: usize,
+}
+
+impl<W: PartialOrd> Ord for MinFirst<W>
+{
+    // weights are checked to be comparable
+    fn cmp(&self, other: &Self) -> Ordering
+    {
+        other.priority.partial_cmp(&self.priority)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.vert.cmp(&self.vert))
+    }
+}
+
+impl<W: PartialOrd> PartialOrd for MinFirst<W>
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering>
+    { Some(self.cmp(other)) }
+}
+
+impl<W: PartialOrd> PartialEq for MinFirst<W>
+{
+    fn eq(&self, other: &Self) -> bool
+    { self.cmp(other) == Ordering::Equal }
+}
+
+impl<W: PartialOrd> Eq for MinFirst<W> {}
+
+type PairResult<'gr, VD, EL, W, E> = WeightResult<Option<(W, Path<'gr, VD, EL>)>, E>;
+type AllPairsResult<'gr, VD, EL, W, E> = WeightResult<Vec<ShortestPaths<'gr, VD, EL, W>>, E>;
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Shortest paths from `from` to all the verticles reachable from it,
+    /// with edges weighed by `weight`. Negative weights are rejected
+    pub fn dijkstra<'gr, WF>(&'gr self, from: VertHandle<'gr, VD, EL>, weight: WF)
+        -> WeightResult<ShortestPaths<'gr, VD, EL, WF::Weight>, WF::Error>
+        where WF: EdgeWeight<EL>,
+    {
+        assert!(from.is_of(self), "'from' is from  different owner");
+
+        let weights = self.non_negative_weights(weight)?;
+        Ok(self.dijkstra_with(from.id(), &weights, None))
+    }
+
+    /// Shortest path from `from` to `to` along with its length, `None` if `to` is unreachable.
+    /// The search stops as soon as `to` is reached
+    pub fn dijkstra_to<'gr, WF>(&'gr self, from: VertHandle<'gr, VD, EL>, to: VertHandle<'gr, VD, EL>, weight: WF)
+        -> PairResult<'gr, VD, EL, WF::Weight, WF::Error>
+        where WF: EdgeWeight<EL>,
+    {
+        assert!(from.is_of(self), "'from' is from  different owner");
+        assert!(to.is_of(self), "'to' is from  different owner");
+
+        let weights = self.non_negative_weights(weight)?;
+        let paths = self.dijkstra_with(from.id(), &weights, Some(to.id()));
+        Ok(paths.distance(to.id())
+            .zip(paths.path_to(to.id())))
+    }
+
+    /// Shortest paths from every verticle, the `i`-th result starts from the verticle `i`.
+    /// Edges are weighed only once
+    pub fn all_pairs_dijkstra<WF>(&self, weight: WF)
+        -> AllPairsResult<'_, VD, EL, WF::Weight, WF::Error>
+        where WF: EdgeWeight<EL>,
+    {
+        let weights = self.non_negative_weights(weight)?;
+        Ok((0..self.verts_cnt())
+            .map(|from| self.dijkstra_with(from, &weights, None))
+            .collect())
+    }
+
+    /// Runs Dijkstra on weights known to be non-negative, stops after settling `target`
+    pub (crate) fn dijkstra_with<W>(&self, from: usize, weights: &EdgeWeights<W>, target: Option<usize>)
+        -> ShortestPaths<'_, VD, EL, W>
+        where W: Weight,
+    { self.dijkstra_through(from, weights, target, |_, _, _| true) }
+
+    /// Same as [Graph::dijkstra_with], but follows only edges `(from, edge_idx, to)`,
+    /// which are `allowed`
+    pub (crate) fn dijkstra_through<W, F>(&self, from: usize, weights: &EdgeWeights<W>, target: Option<usize>, mut allowed: F)
+        -> ShortestPaths<'_, VD, EL, W>
+        where W: Weight,
+              F: FnMut(usize, usize, usize) -> bool,
+    {
+        let mut paths = ShortestPaths::new(self, from);
+        let mut settled = vec![false; self.verts_cnt()];
+        let mut queue = BinaryHeap::from([MinFirst{ priority: W::zero(), vert: from }]);
+
+        while let Some(MinFirst{ vert, .. }) = queue.pop() {
+            // the queue may keep outdated entries of settled verticles
+            if std::mem::replace(&mut settled[vert], true) {
+                continue
+            }
+            if target == Some(vert) {
+                break
+            }
+
+            let edges = self.get_vert(vert)
+                .expect("queue holds valid verticles")
+                .edges();
+            edges.zip(weights.of(vert))
+                .enumerate()
+                .for_each(|(edge_idx, ((_, to), weight))| {
+                    if !settled[to.id()] && allowed(vert, edge_idx, to.id()) && paths.relax(vert, edge_idx, to.id(), *weight) {
+                        let priority = paths.distance[to.id()].expect("relaxed verticle has distance");
+                        queue.push(MinFirst{ priority, vert: to.id() });
+                    }
+                });
+        }
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{test_util::months, read_tgf, ParseWeight, WeightErrorKind};
+
+    use super::*;
+
+    fn roads() -> Graph<String, String>
+    {
+        let input = r"
+            1 Home
+            2 Bakery
+            3 Park
+            4 Office
+            5 Island
+            #
+            1 2 4
+            1 3 1
+            3 2 2
+            2 4 5
+            3 4 8
+            4 1 1
+        ";
+        read_tgf(input.lines())
+            .expect("input is valid")
+    }
+
+    #[test]
+    fn single_source()
+    {
+        let graph = roads();
+        let home = graph.get_vert(0).expect("vert exists");
+        let paths = graph.dijkstra(home, ParseWeight::<u32>::new())
+            .expect("weights are numbers");
+
+        let distances: Vec<Option<u32>> = (0..graph.verts_cnt())
+            .map(|id| paths.distance(id))
+            .collect();
+        assert_eq!(distances, [Some(0), Some(3), Some(1), Some(8), None]);
+        assert!(!paths.is_reachable(4));
+        assert_eq!(paths.source().id(), 0);
+        assert_eq!(paths.parent(1).map(|v| v.id()), Some(2));
+
+        let path = paths.path_to(3)
+            .expect("Office is reachable");
+        let places: Vec<String> = path.verts()
+            .iter()
+            .map(|vert| vert.to_string())
+            .collect();
+        assert_eq!(places, ["Home", "Park", "Bakery", "Office"]);
+        assert_eq!(path.labels(), [&"1", &"2", &"5"]);
+        assert!(paths.path_to(4).is_none());
+    }
+
+    #[test]
+    fn single_pair()
+    {
+        let graph = roads();
+        let office = graph.get_vert(3).expect("vert exists");
+        let bakery = graph.get_vert(1).expect("vert exists");
+        let island = graph.get_vert(4).expect("vert exists");
+
+        let (distance, path) = graph.dijkstra_to(office, bakery, ParseWeight::<f64>::new())
+            .expect("weights are numbers")
+            .expect("Bakery is reachable");
+        assert_eq!(distance, 4.0);
+        assert_eq!(path.ids(), [3, 0, 2, 1]);
+
+        let none = graph.dijkstra_to(office, island, ParseWeight::<f64>::new())
+            .expect("weights are numbers");
+        assert!(none.is_none());
+    }
+
+    #[test]
+    fn all_pairs()
+    {
+        let graph = roads();
+        let all = graph.all_pairs_dijkstra(ParseWeight::<u64>::new())
+            .expect("weights are numbers");
+        assert_eq!(all.len(), 5);
+        assert_eq!(all[3].distance(1), Some(4));
+        assert_eq!(all[1].distance(2), Some(7));
+        assert!((0..4).all(|id| !all[id].is_reachable(4)));
+        assert_eq!(all[4].distance(4), Some(0));
+    }
+
+    #[test]
+    fn negative_weight()
+    {
+        let mut graph = Graph::<(), i32>::new();
+        graph.extend([(), ()]);
+        graph.add_edge(0, 1, 3)
+            .and_then(|g| g.add_edge(1, 0, -1))
+            .expect("verts exist");
+        let start = graph.get_vert(0).expect("vert exists");
+
+        let err = graph.dijkstra(start, |label: &i32| *label)
+            .expect_err("negative weight");
+        assert!(matches!(err.kind(), WeightErrorKind::Negative));
+        assert_eq!((err.from(), err.edge_idx()), (1, 0));
+    }
+
+    #[test]
+    fn matches_bfs_on_unit_weights()
+    {
+        let graph = months();
+        graph.verts()
+            .for_each(|from| {
+                let paths = graph.dijkstra(from, |_: &String| 1usize)
+                    .expect("weights are valid");
+                let tree = graph.bfs_tree(from);
+                assert!((0..graph.verts_cnt()).all(|id| paths.distance(id) == tree.depth(id)));
+            });
+    }
+}
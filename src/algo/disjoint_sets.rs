@@ -0,0 +1,130 @@
+/// Union-find over elements `0..len()`, which tracks connectivity as unions are made.
+///
+/// Uses union by size and path compression, so any sequence of operations
+/// takes almost linear time
+#[derive(Debug, Clone, Default)]
+pub struct DisjointSets
+{
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl DisjointSets
+{
+    /// `len` elements, each in its own set
+    pub fn new(len: usize) -> Self
+    {
+        DisjointSets {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            count: len,
+        }
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize
+    { self.parent.len() }
+
+    pub fn is_empty(&self) -> bool
+    { self.parent.is_empty() }
+
+    /// Number of sets
+    pub fn count(&self) -> usize
+    { self.count }
+
+    /// Adds an element in its own set, returns the element
+    pub fn push(&mut self) -> usize
+    {
+        let elem = self.len();
+        self.parent.push(elem);
+        self.size.push(1);
+        self.count += 1;
+        elem
+    }
+
+    /// Representative of the set containing `elem`, which is the same for all its elements
+    /// until the next union. Panics if there is no such element
+    pub fn find(&mut self, elem: usize) -> usize
+    {
+        let mut root = elem;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // point everything on the way straight to the root
+        let mut curr = elem;
+        while self.parent[curr] != root {
+            curr = std::mem::replace(&mut self.parent[curr], root);
+        }
+        root
+    }
+
+    /// Merges sets of `fst` and `snd`, returns `false` if they were in the same set already
+    pub fn union(&mut self, fst: usize, snd: usize) -> bool
+    {
+        let (fst, snd) = (self.find(fst), self.find(snd));
+        if fst == snd {
+            return false
+        }
+        let (big, small) = when! {
+            self.size[fst] < self.size[snd] => (snd, fst),
+            _ => (fst, snd),
+        };
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+        self.count -= 1;
+        true
+    }
+
+    /// Whether `fst` and `snd` are in the same set
+    pub fn same(&mut self, fst: usize, snd: usize) -> bool
+    { self.find(fst) == self.find(snd) }
+
+    /// Number of elements in the set of `elem`
+    pub fn size_of(&mut self, elem: usize) -> usize
+    {
+        let root = self.find(elem);
+        self.size[root]
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn unions()
+    {
+        let mut sets = DisjointSets::new(5);
+        assert_eq!(sets.count(), 5);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(sets.union(1, 4));
+        assert!(!sets.union(0, 3));
+        assert_eq!(sets.count(), 2);
+        assert!(sets.same(0, 3));
+        assert!(!sets.same(2, 3));
+        assert_eq!(sets.size_of(4), 4);
+        assert_eq!(sets.size_of(2), 1);
+
+        let elem = sets.push();
+        assert_eq!((elem, sets.len(), sets.count()), (5, 6, 3));
+        sets.union(elem, 2);
+        assert_eq!(sets.size_of(2), 2);
+    }
+
+    #[test]
+    fn long_chain()
+    {
+        const SIZE: usize = 100_000;
+        let mut sets = DisjointSets::new(SIZE);
+        (1..SIZE).for_each(|elem| {
+            sets.union(elem - 1, elem);
+        });
+        assert_eq!(sets.count(), 1);
+        let root = sets.find(0);
+        assert!((0..SIZE).all(|elem| sets.find(elem) == root));
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::VecDeque;
+
+use crate::{EdgeWeight, EdgeWeights, Graph, VertHandle, Weight, WeightResult};
+
+/// Arc of a residual network
+#[derive(Debug, Clone, Copy)]
+pub (crate) struct Arc<W>
+{
+    pub (crate) from: usize,
+    pub (crate) to: usize,
+    pub (crate) capacity: W,
+    pub (crate) residual: W,
+}
+
+/// Residual network of a graph. Every edge makes a pair of arcs: the forward one
+/// and the reverse one of zero capacity, so the pair of arc `i` is `i ^ 1`
+#[derive(Debug)]
+pub (crate) struct Residual<W>
+{
+    pub (crate) arcs: Vec<Arc<W>>,
+    // arcs going from every verticle
+    pub (crate) out: Vec<Vec<usize>>,
+    // forward arc of every edge
+    edge_arc: Vec<Vec<usize>>,
+}
+
+impl<W> Residual<W>
+    where W: Weight,
+{
+    pub (crate) fn new<VD, EL>(graph: &Graph<VD, EL>, capacities: &EdgeWeights<W>) -> Self
+    {
+        let mut residual = Residual{ arcs: vec![], out: vec![vec![]; graph.verts_cnt()], edge_arc: vec![] };
+        residual.edge_arc = graph.verts()
+            .map(|from| from.edges()
+                .zip(capacities.of(from.id()))
+                .map(|((_, to), capacity)| {
+                    let arc = residual.arcs.len();
+                    residual.arcs.push(Arc{ from: from.id(), to: to.id(), capacity: *capacity, residual: *capacity });
+                    residual.arcs.push(Arc{ from: to.id(), to: from.id(), capacity: W::zero(), residual: W::zero() });
+                    residual.out[from.id()].push(arc);
+                    residual.out[to.id()].push(arc ^ 1);
+                    arc
+                })
+                .collect())
+            .collect();
+        residual
+    }
+
+    /// Sends `amount` more along `arc`
+    pub (crate) fn push(&mut self, arc: usize, amount: W)
+    {
+        self.arcs[arc].residual = self.arcs[arc].residual - amount;
+        self.arcs[arc ^ 1].residual = self.arcs[arc ^ 1].residual + amount;
+    }
+
+    /// Flow along every edge in the same order as [VertHandle::edges]
+    pub (crate) fn edge_flows(&self) -> Vec<Vec<W>>
+    {
+        self.edge_arc.iter()
+            .map(|arcs| arcs.iter()
+                .map(|arc| self.arcs[*arc].capacity - self.arcs[*arc].residual)
+                .collect())
+            .collect()
+    }
+
+    /// Distances from `source` in arcs, which have residual capacity
+    pub (crate) fn levels(&self, source: usize) -> Vec<Option<usize>>
+    {
+        let mut level = vec![None; self.out.len()];
+        level[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(vert) = queue.pop_front() {
+            let next_level = level[vert].map(|level| level + 1);
+            self.out[vert].iter()
+                .map(|arc| self.arcs[*arc])
+                .filter(|arc| arc.residual > W::zero())
+                .for_each(|arc| if level[arc.to].is_none() {
+                    level[arc.to] = next_level;
+                    queue.push_back(arc.to);
+                });
+        }
+        level
+    }
+}
+
+/// Maximum flow from a source to a sink along with a minimum cut between them, see [Graph::max_flow]
+#[derive(Debug)]
+pub struct MaxFlow<'gr, VD, EL, W>
+{
+    graph: &'gr Graph<VD, EL>,
+    value: W,
+    flow: Vec<Vec<W>>,
+    source_side: Vec<bool>,
+}
+
+impl<'gr, VD, EL, W> MaxFlow<'gr, VD, EL, W>
+    where W: Weight,
+{
+    /// Total flow leaving the source, which equals the capacity of the minimum cut
+    pub fn value(&self) -> W
+    { self.value }
+
+    /// Flow along `edge_idx`-th edge going from `from`
+    pub fn flow(&self, from: usize, edge_idx: usize) -> Option<W>
+    {
+        self.flow.get(from)
+            .and_then(|flow| flow.get(edge_idx))
+            .copied()
+    }
+
+    /// Edges carrying some flow as `(from, label, to, flow)`
+    pub fn flows(&self) -> impl Iterator<Item = (VertHandle<'gr, VD, EL>, &'gr EL, VertHandle<'gr, VD, EL>, W)> + '_
+    {
+        self.graph.verts()
+            .flat_map(|from| from.edges()
+                .zip(&self.flow[from.id()])
+                .filter(|(_, flow)| **flow > W::zero())
+                .map(move |((label, to), flow)| (from, label, to, *flow)))
+    }
+
+    /// Whether `id` is on the source side of the minimum cut
+    pub fn is_source_side(&self, id: usize) -> bool
+    { self.source_side.get(id).copied().unwrap_or(false) }
+
+    /// Verticles reachable from the source in the residual network
+    pub fn source_side(&self) -> Vec<usize>
+    { self.side(true) }
+
+    /// Verticles, which are not on the source side, including the sink
+    pub fn sink_side(&self) -> Vec<usize>
+    { self.side(false) }
+
+    /// Edges going from the source side to the sink side as `(from, label, to)`,
+    /// their capacities sum up to [MaxFlow::value]
+    pub fn cut_edges(&self) -> impl Iterator<Item = (VertHandle<'gr, VD, EL>, &'gr EL, VertHandle<'gr, VD, EL>)> + '_
+    {
+        self.graph.verts()
+            .filter(|from| self.source_side[from.id()])
+            .flat_map(|from| from.edges()
+                .filter(|(_, to)| !self.source_side[to.id()])
+                .map(move |(label, to)| (from, label, to)))
+    }
+
+    /// Flow of `residual`, where no more can be sent from `source`
+    pub (crate) fn from_residual(graph: &'gr Graph<VD, EL>, source: usize, value: W, residual: &Residual<W>) -> Self
+    {
+        let source_side = residual.levels(source)
+            .iter()
+            .map(Option::is_some)
+            .collect();
+        MaxFlow{ graph, value, flow: residual.edge_flows(), source_side }
+    }
+
+    fn side(&self, source_side: bool) -> Vec<usize>
+    {
+        (0..self.source_side.len())
+            .filter(|id| self.source_side[*id] == source_side)
+            .collect()
+    }
+}
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Maximum flow from `source` to `sink` by Dinic's algorithm, where `capacity` limits
+    /// the flow along every edge. Negative capacities are rejected.
+    /// Panics if `source` and `sink` are the same verticle
+    pub fn max_flow<'gr, WF>(&'gr self, source: VertHandle<'gr, VD, EL>, sink: VertHandle<'gr, VD, EL>, capacity: WF)
+        -> WeightResult<MaxFlow<'gr, VD, EL, WF::Weight>, WF::Error>
+        where WF: EdgeWeight<EL>,
+    {
+        assert!(source.is_of(self), "'source' is from  different owner");
+        assert!(sink.is_of(self), "'sink' is from  different owner");
+        assert_ne!(source.id(), sink.id(), "'source' and 'sink' are the same verticle");
+
+        let capacities = self.non_negative_weights(capacity)?;
+        let mut residual = Residual::new(self, &capacities);
+        let (source, sink) = (source.id(), sink.id());
+        let mut value = WF::Weight::zero();
+
+        loop {
+            let mut level = residual.levels(source);
+            if level[sink].is_none() {
+                return Ok(MaxFlow::from_residual(self, source, value, &residual))
+            }
+
+            // blocking flow: augmenting paths along arcs, which go one level further
+            let mut next = vec![0; self.verts_cnt()];
+            let mut path: Vec<usize> = vec![];
+            let mut vert = source;
+            loop {
+                if vert == sink {
+                    let bottleneck = path.iter()
+                        .map(|arc| residual.arcs[*arc].residual)
+                        .reduce(|min, residual| when! {
+                            residual < min => residual,
+                            _ => min,
+                        })
+                        .expect("sink differs from source");
+                    path.iter()
+                        .for_each(|arc| residual.push(*arc, bottleneck));
+                    value = value + bottleneck;
+                    path.clear();
+                    vert = source;
+                    continue
+                }
+
+                let admissible = residual.out[vert][next[vert]..].iter()
+                    .position(|arc| {
+                        let arc = residual.arcs[*arc];
+                        arc.residual > WF::Weight::zero() && level[arc.to] == level[vert].map(|level| level + 1)
+                    });
+                match admissible {
+                    Some(skipped) => {
+                        next[vert] += skipped;
+                        let arc = residual.out[vert][next[vert]];
+                        path.push(arc);
+                        vert = residual.arcs[arc].to;
+                    },
+                    None => {
+                        // dead end, it's never visited again in this phase
+                        level[vert] = None;
+                        let Some(arc) = path.pop() else {
+                            break
+                        };
+                        vert = residual.arcs[arc].from;
+                        next[vert] += 1;
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{read_tgf, ParseWeight};
+
+    use super::*;
+
+    // the classic example of CLRS
+    fn pipelines() -> Graph<String, String>
+    {
+        let input = r"
+            1 s
+            2 v1
+            3 v2
+            4 v3
+            5 v4
+            6 t
+            7 tank
+            #
+            1 2 16
+            1 3 13
+            3 2 4
+            2 4 12
+            4 3 9
+            3 5 14
+            5 4 7
+            4 6 20
+            5 6 4
+            7 1 100
+            6 6 5
+        ";
+        read_tgf(input.lines())
+            .expect("input is valid")
+    }
+
+    #[test]
+    fn flow_and_cut()
+    {
+        let graph = pipelines();
+        let source = graph.get_vert(0).expect("vert exists");
+        let sink = graph.get_vert(5).expect("vert exists");
+        let flow = graph.max_flow(source, sink, ParseWeight::<u32>::new())
+            .expect("capacities are numbers");
+        assert_eq!(flow.value(), 23);
+
+        // flow is conserved and doesn't exceed capacities
+        let mut balance = vec![0i64; graph.verts_cnt()];
+        flow.flows()
+            .for_each(|(from, label, to, amount)| {
+                assert!(amount <= label.parse().expect("label is a number"));
+                balance[from.id()] -= amount as i64;
+                balance[to.id()] += amount as i64;
+            });
+        assert_eq!(balance, [-23, 0, 0, 0, 0, 23, 0]);
+        assert_eq!(flow.flow(9, 0), None);
+        assert_eq!(flow.flow(6, 0), Some(0));
+
+        assert_eq!(flow.source_side(), [0, 1, 2, 4]);
+        assert_eq!(flow.sink_side(), [3, 5, 6]);
+        assert!(!flow.is_source_side(6));
+        let cut: Vec<(usize, usize)> = flow.cut_edges()
+            .map(|(from, _, to)| (from.id(), to.id()))
+            .collect();
+        assert_eq!(cut, [(1, 3), (4, 3), (4, 5)]);
+        let capacity: u32 = flow.cut_edges()
+            .map(|(_, label, _)| label.parse::<u32>().expect("label is a number"))
+            .sum();
+        assert_eq!(capacity, flow.value());
+    }
+
+    #[test]
+    fn parallel_and_fractional()
+    {
+        let mut graph = Graph::<(), f64>::new();
+        graph.extend([(), (), ()]);
+        graph.add_edge(0, 1, 1.5)
+            .and_then(|g| g.add_edge(0, 1, 0.5))
+            .and_then(|g| g.add_edge(1, 2, 3.0))
+            .and_then(|g| g.add_edge(2, 0, 10.0))
+            .expect("verts exist");
+        let source = graph.get_vert(0).expect("vert exists");
+        let sink = graph.get_vert(2).expect("vert exists");
+
+        let flow = graph.max_flow(source, sink, |label: &f64| *label)
+            .expect("closure can't fail");
+        assert_eq!(flow.value(), 2.0);
+        assert_eq!(flow.cut_edges().count(), 2);
+        assert_eq!(flow.source_side(), [0]);
+
+        let back = graph.max_flow(sink, source, |label: &f64| *label)
+            .expect("closure can't fail");
+        assert_eq!(back.value(), 10.0);
+        assert!(graph.max_flow(source, sink, |label: &f64| -*label).is_err());
+    }
+
+    #[test]
+    fn disconnected()
+    {
+        let mut graph = Graph::<(), u8>::new();
+        graph.extend([(), (), ()]);
+        graph.add_edge(0, 1, 7)
+            .expect("verts exist");
+        let source = graph.get_vert(0).expect("vert exists");
+        let sink = graph.get_vert(2).expect("vert exists");
+
+        let flow = graph.max_flow(source, sink, |label: &u8| *label)
+            .expect("closure can't fail");
+        assert_eq!(flow.value(), 0);
+        assert_eq!(flow.flows().count(), 0);
+        assert_eq!(flow.source_side(), [0, 1]);
+        assert_eq!(flow.cut_edges().count(), 0);
+    }
+}
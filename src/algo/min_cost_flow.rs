@@ -0,0 +1,241 @@
+use std::{collections::BinaryHeap, error::Error, fmt::{Debug, Display}};
+
+use super::{all_pairs::reduced_weight, dijkstra::MinFirst, flow::Residual};
+use crate::{EdgeWeight, Graph, MaxFlow, ShortestPathError, VertHandle, Weight, WeightError};
+
+/// Error of [Graph::min_cost_flow], where capacities fail with `CE` and costs with `WE`
+#[derive(Debug)]
+pub enum MinCostFlowError<'gr, VD, EL, W, CE, WE>
+{
+    /// Capacity isn't known or is negative
+    Capacity(WeightError<CE>),
+    /// Cost isn't known, or edges, which have capacity, make a negative cycle
+    Cost(ShortestPathError<'gr, VD, EL, W, WE>),
+}
+
+impl<VD, EL, W, CE, WE> Display for MinCostFlowError<'_, VD, EL, W, CE, WE>
+    where VD: Display,
+          EL: Display,
+          W: Debug,
+          CE: Display,
+          WE: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self {
+            MinCostFlowError::Capacity(err) => Display::fmt(err, f),
+            MinCostFlowError::Cost(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl<VD, EL, W, CE, WE> Error for MinCostFlowError<'_, VD, EL, W, CE, WE>
+    where VD: Debug + Display,
+          EL: Debug + Display,
+          W: Debug,
+          CE: Debug + Display,
+          WE: Debug + Display,
+{}
+
+type CostFlowResult<'gr, VD, EL, W, CE, WE> = Result<(W, MaxFlow<'gr, VD, EL, W>), MinCostFlowError<'gr, VD, EL, W, CE, WE>>;
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Maximum flow from `source` to `sink` of the least total cost along with that cost,
+    /// found by successive shortest paths. `capacity` limits the flow along every edge,
+    /// `cost` is the price of a unit of flow along it.
+    ///
+    /// Negative capacities are rejected. Costs may be negative, but fails with a negative cycle
+    /// if edges of positive capacity make one. Panics if `source` and `sink` are the same verticle
+    pub fn min_cost_flow<'gr, CF, WF>(&'gr self, source: VertHandle<'gr, VD, EL>, sink: VertHandle<'gr, VD, EL>, capacity: CF, cost: WF)
+        -> CostFlowResult<'gr, VD, EL, WF::Weight, CF::Error, WF::Error>
+        where CF: EdgeWeight<EL, Weight = WF::Weight>,
+              WF: EdgeWeight<EL>,
+    {
+        assert!(source.is_of(self), "'source' is from  different owner");
+        assert!(sink.is_of(self), "'sink' is from  different owner");
+        assert_ne!(source.id(), sink.id(), "'source' and 'sink' are the same verticle");
+
+        let capacities = self.non_negative_weights(capacity)
+            .map_err(MinCostFlowError::Capacity)?;
+        let costs = self.edge_weights(cost)
+            .map_err(|err| MinCostFlowError::Cost(err.into()))?;
+        let zero = WF::Weight::zero();
+
+        let mut residual = Residual::new(self, &capacities);
+        // reverse arcs refund the cost of their pairs
+        let arc_cost: Vec<WF::Weight> = self.verts()
+            .flat_map(|from| costs.of(from.id()))
+            .flat_map(|cost| [*cost, zero - *cost])
+            .collect();
+
+        // potentials keep costs of residual arcs non-negative, so Dijkstra can be used.
+        // Edges without capacity never get into the residual network, so they are skipped
+        let mut potential = vec![Some(zero); self.verts_cnt()];
+        if costs.find_negative().is_some() {
+            self.bellman_ford_through(&mut potential, &costs, |from, edge_idx, _| capacities.of(from)[edge_idx] > zero)
+                .map_err(|cycle| MinCostFlowError::Cost(ShortestPathError::NegativeCycle(cycle)))?;
+        }
+        let mut potential: Vec<WF::Weight> = potential.into_iter()
+            .map(|h| h.expect("every verticle has a potential"))
+            .collect();
+
+        let (source, sink) = (source.id(), sink.id());
+        let (mut value, mut total) = (zero, zero);
+        loop {
+            // the cheapest augmenting path by reduced costs
+            let mut distance: Vec<Option<WF::Weight>> = vec![None; self.verts_cnt()];
+            let mut parent = vec![None; self.verts_cnt()];
+            distance[source] = Some(zero);
+            let mut queue = BinaryHeap::from([MinFirst{ priority: zero, vert: source }]);
+
+            while let Some(MinFirst{ priority, vert }) = queue.pop() {
+                let dist = distance[vert].expect("queued verticles have distance");
+                if priority > dist {
+                    continue
+                }
+                residual.out[vert].iter()
+                    .filter(|arc| residual.arcs[**arc].residual > zero)
+                    .for_each(|arc| {
+                        let to = residual.arcs[*arc].to;
+                        let through = dist + reduced_weight(arc_cost[*arc], potential[vert], potential[to]);
+                        if distance[to].is_none_or(|dist| through < dist) {
+                            distance[to] = Some(through);
+                            parent[to] = Some(*arc);
+                            queue.push(MinFirst{ priority: through, vert: to });
+                        }
+                    });
+            }
+
+            if distance[sink].is_none() {
+                return Ok((total, MaxFlow::from_residual(self, source, value, &residual)))
+            }
+            potential.iter_mut()
+                .zip(&distance)
+                .for_each(|(h, dist)| if let Some(dist) = dist {
+                    *h = *h + *dist;
+                });
+
+            let mut path = vec![];
+            let mut curr = sink;
+            while let Some(arc) = parent[curr] {
+                path.push(arc);
+                curr = residual.arcs[arc].from;
+            }
+            let bottleneck = path.iter()
+                .map(|arc| residual.arcs[*arc].residual)
+                .reduce(|min, residual| when! {
+                    residual < min => residual,
+                    _ => min,
+                })
+                .expect("sink differs from source");
+            path.iter()
+                .for_each(|arc| {
+                    residual.push(*arc, bottleneck);
+                    total = total + bottleneck * arc_cost[*arc];
+                });
+            value = value + bottleneck;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::convert::Infallible;
+
+    use super::*;
+
+    // labels are (capacity, cost)
+    type Network = Graph<&'static str, (i32, i32)>;
+
+    fn network(verts: &[&'static str], edges: &[(usize, usize, i32, i32)]) -> Network
+    {
+        let mut graph = Graph::new();
+        graph.extend(verts.iter().copied());
+        edges.iter()
+            .for_each(|(from, to, capacity, cost)| {
+                graph.add_edge(*from, *to, (*capacity, *cost))
+                    .expect("verts exist");
+            });
+        graph
+    }
+
+    fn solve(graph: &Network, source: usize, sink: usize) -> CostFlowResult<'_, &'static str, (i32, i32), i32, Infallible, Infallible>
+    {
+        let source = graph.get_vert(source).expect("vert exists");
+        let sink = graph.get_vert(sink).expect("vert exists");
+        graph.min_cost_flow(source, sink, |label: &(i32, i32)| label.0, |label: &(i32, i32)| label.1)
+    }
+
+    #[test]
+    fn assignment()
+    {
+        let workers = ["ann", "bob", "cid"];
+        let costs = [[4, 1, 3], [2, 0, 5], [3, 2, 2]];
+        let mut edges = vec![];
+        (0..3).for_each(|worker| {
+            edges.push((0, worker + 1, 1, 0));
+            edges.push((worker + 4, 7, 1, 0));
+            (0..3).for_each(|job| edges.push((worker + 1, job + 4, 1, costs[worker][job])));
+        });
+        let graph = network(&["start", workers[0], workers[1], workers[2], "cook", "drive", "paint", "end"], &edges);
+
+        let (cost, flow) = solve(&graph, 0, 7)
+            .expect("there are no negative cycles");
+        assert_eq!((cost, flow.value()), (5, 3));
+
+        let mut assigned: Vec<(&str, &str)> = flow.flows()
+            .filter(|(from, _, to, _)| from.id() != 0 && to.id() != 7)
+            .map(|(from, _, to, amount)| {
+                assert_eq!(amount, 1);
+                (**from, **to)
+            })
+            .collect();
+        assigned.sort();
+        assert_eq!(assigned, [("ann", "drive"), ("bob", "cook"), ("cid", "paint")]);
+        assert_eq!(flow.source_side(), [0]);
+    }
+
+    #[test]
+    fn negative_costs()
+    {
+        let mut graph = network(&["s", "a", "b", "t"], &[
+            (0, 1, 2, 1),
+            (0, 2, 2, 3),
+            (1, 3, 1, 1),
+            (1, 2, 2, -1),
+            (2, 3, 3, 1),
+        ]);
+        let (cost, flow) = solve(&graph, 0, 3)
+            .expect("there are no negative cycles");
+        assert_eq!((cost, flow.value()), (11, 4));
+        assert_eq!(flow.flow(1, 1), Some(1));
+
+        // the maximum flow is still the same as Dinic's one
+        let source = graph.get_vert(0).expect("vert exists");
+        let sink = graph.get_vert(3).expect("vert exists");
+        let dinic = graph.max_flow(source, sink, |label: &(i32, i32)| label.0)
+            .expect("closure can't fail");
+        assert_eq!(dinic.value(), flow.value());
+
+        graph.add_edge(2, 1, (1, 0))
+            .expect("verts exist");
+        match solve(&graph, 0, 3) {
+            Err(MinCostFlowError::Cost(ShortestPathError::NegativeCycle(cycle))) => assert_eq!(cycle.weight(), -1),
+            Err(err) => panic!("Unexpected error: {err:?}"),
+            Ok(_) => panic!("a -> b -> a is a negative cycle"),
+        }
+
+        // the cycle can't carry any flow
+        graph.set_label(2, 1, (0, 0))
+            .expect("edge exists");
+        let (cost, flow) = solve(&graph, 0, 3)
+            .expect("edges with capacity make no negative cycles");
+        assert_eq!((cost, flow.value()), (11, 4));
+
+        graph.set_label(2, 1, (-1, 0))
+            .expect("edge exists");
+        assert!(matches!(solve(&graph, 0, 3), Err(MinCostFlowError::Capacity(_))));
+    }
+}
@@ -0,0 +1,62 @@
+mod quotient;
+pub use quotient::Quotient;
+
+mod weight;
+pub use weight::{Weight, EdgeWeight, EdgeWeights, TryWeightFn, try_weight, ParseWeight, ParseWeightError, WeightError, WeightErrorKind, WeightResult};
+
+mod dfs;
+pub use dfs::{DfsEvent, DFSIterator};
+
+mod path;
+pub use path::Path;
+
+mod bfs;
+pub use bfs::{BfsStep, BFSStepIterator, BfsTree};
+
+mod bidirectional;
+pub use bidirectional::BidirectionalBFS;
+
+mod walk;
+pub use walk::{RandomWalk, DeadEnd};
+
+mod chordal;
+
+mod deepening;
+pub use deepening::DepthLimitedDFS;
+
+mod toposort;
+pub use toposort::CycleError;
+
+mod components;
+pub use components::Components;
+
+mod disjoint_sets;
+pub use disjoint_sets::DisjointSets;
+
+mod scc;
+
+mod wcc;
+
+mod dijkstra;
+pub use dijkstra::ShortestPaths;
+
+mod bellman_ford;
+pub use bellman_ford::{ShortestPathError, NegativeCycle};
+
+mod astar;
+
+mod all_pairs;
+pub use all_pairs::DistanceMatrix;
+
+mod yen;
+
+mod spanning;
+pub use spanning::SpanningForest;
+
+mod flow;
+pub use flow::MaxFlow;
+
+mod min_cost_flow;
+
+mod visit;
+pub use visit::{Control, Visitor, VertVisitor, vert_visitor, EdgeVisitor, edge_visitor};
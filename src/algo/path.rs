@@ -0,0 +1,91 @@
+use crate::{Graph, VertHandle};
+
+/// Walk through a graph: its verticles and labels of the edges between them,
+/// so there is always one label less than verticles
+#[derive(Debug)]
+pub struct Path<'gr, VD, EL>
+{
+    verts: Vec<VertHandle<'gr, VD, EL>>,
+    labels: Vec<&'gr EL>,
+}
+
+impl<VD, EL> Clone for Path<'_, VD, EL>
+{
+    fn clone(&self) -> Self
+    { Path{ verts: self.verts.clone(), labels: self.labels.clone() } }
+}
+
+impl<'gr, VD, EL> Path<'gr, VD, EL>
+{
+    /// Path of no edges
+    pub (crate) fn new(start: VertHandle<'gr, VD, EL>) -> Self
+    { Path{ verts: vec![start], labels: vec![] } }
+
+    pub (crate) fn push(&mut self, label: &'gr EL, to: VertHandle<'gr, VD, EL>)
+    {
+        self.labels.push(label);
+        self.verts.push(to);
+    }
+
+    /// Rebuilds path to `target` going back by `parent_edge`, which returns
+    /// the edge leading to the verticle as `(from, edge_idx)`, until it returns `None`
+    pub (crate) fn from_parents<F>(graph: &'gr Graph<VD, EL>, target: usize, mut parent_edge: F) -> Self
+        where F: FnMut(usize) -> Option<(usize, usize)>,
+    {
+        let mut edges = vec![];
+        let mut curr = target;
+        while let Some((from, edge_idx)) = parent_edge(curr) {
+            edges.push((from, edge_idx));
+            curr = from;
+        }
+
+        let start = graph.get_vert(curr)
+            .expect("path should go through valid verticles");
+        edges.into_iter()
+            .rev()
+            .fold(Path::new(start), |mut path, (from, edge_idx)| {
+                let (label, to) = path.target()
+                    .edge(edge_idx)
+                    .expect("path should go through valid edges");
+                debug_assert_eq!(path.target().id(), from);
+                path.push(label, to);
+                path
+            })
+    }
+
+    pub fn verts(&self) -> &[VertHandle<'gr, VD, EL>]
+    { &self.verts }
+
+    pub fn labels(&self) -> &[&'gr EL]
+    { &self.labels }
+
+    /// Edges of the path as `(from, label, to)`
+    pub fn edges(&self) -> impl Iterator<Item = (VertHandle<'gr, VD, EL>, &'gr EL, VertHandle<'gr, VD, EL>)> + '_
+    {
+        self.verts.windows(2)
+            .zip(&self.labels)
+            .map(|(ends, label)| (ends[0], *label, ends[1]))
+    }
+
+    pub fn source(&self) -> VertHandle<'gr, VD, EL>
+    { self.verts[0] }
+
+    pub fn target(&self) -> VertHandle<'gr, VD, EL>
+    { self.verts[self.verts.len() - 1] }
+
+    /// Number of edges
+    pub fn len(&self) -> usize
+    { self.labels.len() }
+
+    /// Whether the path has no edges
+    pub fn is_empty(&self) -> bool
+    { self.labels.is_empty() }
+
+    /// Ids of the verticles
+    pub fn ids(&self) -> Vec<usize>
+    {
+        self.verts.iter()
+            .map(|vert| vert.id())
+            .collect()
+    }
+}
@@ -0,0 +1,178 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{Graph, VertHandle};
+
+/// Result of [Graph::quotient]: the quotient graph together with
+/// the class assigned to every verticle of the original graph
+#[derive(Debug)]
+pub struct Quotient<VD, EL>
+{
+    graph: Graph<VD, EL>,
+    class_of: Vec<usize>,
+}
+
+impl<VD, EL> Quotient<VD, EL>
+{
+    pub fn graph(&self) -> &Graph<VD, EL>
+    { &self.graph }
+
+    pub fn into_graph(self) -> Graph<VD, EL>
+    { self.graph }
+
+    /// Id of the quotient verticle the original verticle `vert_id` was merged into
+    pub fn class_of(&self, vert_id: usize) -> Option<usize>
+    { self.class_of.get(vert_id).copied() }
+}
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Builds the quotient graph of partition given by `class_of`.
+    ///
+    /// Every class becomes a single verticle, whose data is produced by `aggregate_data`
+    /// from the class key and all of its members. All the edges going from one class to another
+    /// (or inside a single class) are passed to `aggregate_labels`, which decides the label
+    /// of the resulting edge, or drops it by returning `None`.
+    ///
+    /// Classes are numbered in order of the first appearance of their members in [Graph::verts],
+    /// edges between them are added in order of the first original edge.
+    pub fn quotient<'gr, K, QD, QL, FK, FD, FL>(
+        &'gr self,
+        mut class_of: FK,
+        mut aggregate_data: FD,
+        mut aggregate_labels: FL) -> Quotient<QD, QL>
+        where K: Eq + Hash,
+              FK: FnMut(VertHandle<'gr, VD, EL>) -> K,
+              FD: FnMut(&K, Vec<VertHandle<'gr, VD, EL>>) -> QD,
+              FL: FnMut(&K, &K, Vec<&'gr EL>) -> Option<QL>,
+    {
+        let mut class_by_key = HashMap::<K, usize>::new();
+        let mut members = Vec::<Vec<VertHandle<'gr, VD, EL>>>::new();
+
+        let vert_class: Vec<usize> = self.verts()
+            .map(|vert| {
+                let new_class = class_by_key.len();
+                let class = *class_by_key.entry(class_of(vert))
+                    .or_insert(new_class);
+                if class == new_class {
+                    members.push(vec![]);
+                }
+                members[class].push(vert);
+                class
+            })
+            .collect();
+
+        // keys are owned by the map, so put them back in order of classes
+        let mut keys: Vec<Option<K>> = std::iter::repeat_with(|| None)
+            .take(class_by_key.len())
+            .collect();
+        class_by_key.into_iter()
+            .for_each(|(key, class)| keys[class] = Some(key));
+        let keys: Vec<K> = keys.into_iter()
+            .map(|key| key.expect("every class has a key"))
+            .collect();
+
+        // group edges by pair of classes they connect
+        let mut group_by_classes = HashMap::<(usize, usize), usize>::new();
+        let mut groups = Vec::<(usize, usize, Vec<&'gr EL>)>::new();
+        self.verts()
+            .for_each(|from| from.edges()
+                .for_each(|(label, to)| {
+                    let classes = (vert_class[from.id()], vert_class[to.id()]);
+                    let new_group = groups.len();
+                    let group = *group_by_classes.entry(classes)
+                        .or_insert(new_group);
+                    if group == new_group {
+                        groups.push((classes.0, classes.1, vec![]));
+                    }
+                    groups[group].2.push(label);
+                }));
+
+        let mut graph = Graph::new();
+        graph.extend(keys.iter()
+            .zip(members)
+            .map(|(key, members)| aggregate_data(key, members)));
+
+        groups.into_iter()
+            .filter_map(|(from, to, labels)| aggregate_labels(&keys[from], &keys[to], labels)
+                .map(|label| (from, to, label)))
+            .for_each(|(from, to, label)| {
+                graph.add_edge(from, to, label)
+                    .expect("quotient verticles are all present");
+            });
+
+        Quotient{ graph, class_of: vert_class }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::test_util::months;
+
+    #[test]
+    fn months_by_season()
+    {
+        let graph = months();
+
+        let season = |month: &str| match month {
+            "December" | "January" => "winter",
+            "March" | "April" | "May" => "spring",
+            "June" => "summer",
+            _ => "autumn",
+        };
+
+        let quotient = graph.quotient(
+            |vert| season(&vert),
+            |season, members| format!("{season}: {}", members.len()),
+            |from, to, labels| when! {
+                from == to => None,
+                _ => Some(labels.len()),
+            });
+
+        let seasons: Vec<String> = quotient.graph()
+            .verts()
+            .map(|vert| vert.to_string())
+            .collect();
+        assert_eq!(seasons, ["winter: 2", "spring: 3", "summer: 1", "autumn: 1"]);
+
+        let edges: Vec<(usize, usize, usize)> = quotient.graph()
+            .verts()
+            .flat_map(|from| from.edges()
+                .map(move |(cnt, to)| (from.id(), to.id(), *cnt)))
+            .collect();
+        // winter -> spring: Jan -> March, Dec -> April
+        // spring -> ...: only internal edges, which are dropped
+        assert_eq!(edges, [(0, 1, 2), (2, 1, 1), (2, 0, 1), (3, 0, 1), (3, 2, 1)]);
+
+        let december = graph.verts()
+            .find(|vert| vert.as_str() == "December")
+            .expect("there is December");
+        assert_eq!(quotient.class_of(december.id()), Some(0));
+        assert_eq!(quotient.class_of(graph.verts_cnt()), None);
+    }
+
+    #[test]
+    fn single_class_keeps_loops()
+    {
+        let mut graph = crate::Graph::<u32, u32>::new();
+        graph.extend([1, 2, 3]);
+        graph.add_edge(0, 1, 10)
+            .and_then(|g| g.add_edge(1, 2, 20))
+            .and_then(|g| g.add_edge(2, 0, 30))
+            .expect("verts exist");
+
+        let quotient = graph.quotient(
+            |_| (),
+            |_, members| members.into_iter().map(|v| **v).sum::<u32>(),
+            |_, _, labels| Some(labels.into_iter().sum::<u32>()));
+        let graph = quotient.into_graph();
+
+        assert_eq!(graph.verts_cnt(), 1);
+        let vert = graph.get_vert(0).expect("single class");
+        assert_eq!(**vert, 6);
+        let edges: Vec<_> = vert.edges()
+            .map(|(label, to)| (*label, to.id()))
+            .collect();
+        assert_eq!(edges, [(60, 0)]);
+    }
+}
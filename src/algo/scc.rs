@@ -0,0 +1,167 @@
+use crate::{Components, EdgeIter, Graph, Quotient, VertHandle};
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Strongly connected components found by Tarjan's algorithm.
+    /// The search is iterative, so deep graphs don't overflow the stack
+    pub fn strongly_connected_components(&self) -> Components
+    {
+        let mut tarjan = Tarjan {
+            index: vec![UNVISITED; self.verts_cnt()],
+            low_link: vec![0; self.verts_cnt()],
+            on_stack: vec![false; self.verts_cnt()],
+            stack: vec![],
+            time: 0,
+        };
+        let mut calls: Vec<(VertHandle<'_, VD, EL>, EdgeIter<'_, VD, EL>)> = vec![];
+        let mut labels = vec![0; self.verts_cnt()];
+        let mut found = 0;
+
+        for root in self.verts() {
+            if tarjan.index[root.id()] != UNVISITED {
+                continue
+            }
+            tarjan.visit(root.id());
+            calls.push((root, root.edges()));
+
+            while let Some((vert, edges)) = calls.last_mut() {
+                let vert = vert.id();
+                if let Some((_, to)) = edges.next() {
+                    if tarjan.index[to.id()] == UNVISITED {
+                        tarjan.visit(to.id());
+                        calls.push((to, to.edges()));
+                    } else if tarjan.on_stack[to.id()] {
+                        tarjan.low_link[vert] = tarjan.low_link[vert].min(tarjan.index[to.id()]);
+                    }
+                    continue
+                }
+
+                calls.pop();
+                if let Some((parent, _)) = calls.last() {
+                    tarjan.low_link[parent.id()] = tarjan.low_link[parent.id()].min(tarjan.low_link[vert]);
+                }
+                // the verticle is the root of a component, which consists of everything above it
+                if tarjan.low_link[vert] == tarjan.index[vert] {
+                    while let Some(member) = tarjan.stack.pop() {
+                        tarjan.on_stack[member] = false;
+                        labels[member] = found;
+                        if member == vert {
+                            break
+                        }
+                    }
+                    found += 1;
+                }
+            }
+        }
+        Components::from_labels(&labels)
+    }
+
+    /// Acyclic graph of strongly connected components. Every verticle holds ids of members
+    /// of its component, every edge is labeled by the number of original edges between
+    /// the components. Verticles of the condensation have the same ids as the components
+    /// returned by [Graph::strongly_connected_components]
+    pub fn condensation(&self) -> Quotient<Vec<usize>, usize>
+    {
+        let components = self.strongly_connected_components();
+        self.quotient(
+            |vert| components.component_of(vert.id()),
+            |_, members| members.iter()
+                .map(|vert| vert.id())
+                .collect(),
+            |from, to, labels| when! {
+                from == to => None,
+                _ => Some(labels.len()),
+            })
+    }
+}
+
+const UNVISITED: usize = usize::MAX;
+
+struct Tarjan
+{
+    index: Vec<usize>,
+    low_link: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    time: usize,
+}
+
+impl Tarjan
+{
+    fn visit(&mut self, vert: usize)
+    {
+        self.index[vert] = self.time;
+        self.low_link[vert] = self.time;
+        self.time += 1;
+        self.on_stack[vert] = true;
+        self.stack.push(vert);
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::test_util::{months, graph_with};
+
+    use super::*;
+
+    #[test]
+    fn components()
+    {
+        // cycles 0 -> 1 -> 2 -> 0 and 3 <-> 4, 5 has only a loop, 6 is alone
+        let graph = graph_with(7, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (5, 5), (4, 5), (6, 0)]);
+        let components = graph.strongly_connected_components();
+
+        assert_eq!(components.count(), 4);
+        let all: Vec<&[usize]> = components.iter().collect();
+        assert_eq!(all, [&[0, 1, 2][..], &[3, 4], &[5], &[6]]);
+        assert_eq!(components.component_of(4), Some(1));
+        assert_eq!(components.component_of(7), None);
+        assert!(components.same(0, 2));
+        assert!(!components.same(2, 3));
+    }
+
+    #[test]
+    fn condensation()
+    {
+        let graph = graph_with(7, &[(0, 1), (1, 2), (2, 0), (2, 3), (1, 4), (3, 4), (4, 3), (5, 5), (4, 5), (6, 0)]);
+        let condensation = graph.condensation();
+        let dag = condensation.graph();
+
+        let members: Vec<Vec<usize>> = dag.verts()
+            .map(|vert| vert.to_vec())
+            .collect();
+        assert_eq!(members, [vec![0, 1, 2], vec![3, 4], vec![5], vec![6]]);
+
+        let edges: Vec<(usize, usize, usize)> = dag.verts()
+            .flat_map(|from| from.edges()
+                .map(move |(cnt, to)| (from.id(), to.id(), *cnt)))
+            .collect();
+        assert_eq!(edges, [(0, 1, 2), (1, 2, 1), (3, 0, 1)]);
+        assert!(dag.toposort().is_ok());
+        assert_eq!(condensation.class_of(4), Some(1));
+    }
+
+    #[test]
+    fn months_are_acyclic()
+    {
+        let graph = months();
+        assert_eq!(graph.strongly_connected_components().count(), graph.verts_cnt());
+    }
+
+    #[test]
+    fn deep_cycle()
+    {
+        const SIZE: usize = 100_000;
+        let mut graph = Graph::<(), ()>::new();
+        graph.extend(std::iter::repeat_n((), SIZE));
+        (0..SIZE).for_each(|id| {
+            graph.add_edge(id, (id + 1) % SIZE, ())
+                .expect("verts exist");
+        });
+
+        let components = graph.strongly_connected_components();
+        assert_eq!(components.count(), 1);
+        assert_eq!(components.members(0).map(<[usize]>::len), Some(SIZE));
+    }
+}
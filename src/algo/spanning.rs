@@ -0,0 +1,248 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use super::dijkstra::MinFirst;
+use crate::{DisjointSets, EdgeWeight, Graph, VertHandle, Weight, WeightResult};
+
+/// Minimum spanning forest of the undirected view of a graph, see [Graph::kruskal] and [Graph::prim].
+/// There is a tree for every weakly connected component
+#[derive(Debug)]
+pub struct SpanningForest<'gr, VD, EL, W>
+{
+    graph: &'gr Graph<VD, EL>,
+    // chosen edges as (from, edge_idx)
+    edges: Vec<(usize, usize)>,
+    weight: W,
+}
+
+impl<'gr, VD, EL, W> SpanningForest<'gr, VD, EL, W>
+    where W: Weight,
+{
+    /// Chosen edges as `(from, label, to)` in their original direction
+    pub fn edges(&self) -> impl Iterator<Item = (VertHandle<'gr, VD, EL>, &'gr EL, VertHandle<'gr, VD, EL>)> + '_
+    {
+        self.edges.iter()
+            .map(|(from, edge_idx)| {
+                let from = self.graph.get_vert(*from)
+                    .expect("forest has valid verticles");
+                let (label, to) = from.edge(*edge_idx)
+                    .expect("forest has valid edges");
+                (from, label, to)
+            })
+    }
+
+    /// Chosen edges as `(from, edge_idx)`
+    pub fn edge_ids(&self) -> &[(usize, usize)]
+    { &self.edges }
+
+    /// Total weight of the chosen edges
+    pub fn weight(&self) -> W
+    { self.weight }
+
+    /// Number of trees, which is the number of weakly connected components
+    pub fn trees_cnt(&self) -> usize
+    { self.graph.verts_cnt() - self.edges.len() }
+
+    /// The forest as a graph of the same verticles with only the chosen edges
+    pub fn to_graph(&self) -> Graph<VD, EL>
+        where VD: Clone,
+              EL: Clone,
+    {
+        let mut forest = Graph::new();
+        forest.extend(self.graph.verts()
+            .map(|vert| (**vert).clone()));
+        self.edges()
+            .for_each(|(from, label, to)| {
+                forest.add_edge(from.id(), to.id(), label.clone())
+                    .expect("forest has the same verticles");
+            });
+        forest
+    }
+}
+
+type ForestResult<'gr, VD, EL, W, E> = WeightResult<SpanningForest<'gr, VD, EL, W>, E>;
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Minimum spanning forest by Kruskal's algorithm. Edge directions are ignored, loops are never chosen.
+    /// Of edges of equal weight the earlier ones in [Graph::verts] and their edges are preferred
+    pub fn kruskal<WF>(&self, weight: WF) -> ForestResult<'_, VD, EL, WF::Weight, WF::Error>
+        where WF: EdgeWeight<EL>,
+    {
+        let weights = self.edge_weights(weight)?;
+        let mut edges: Vec<(usize, usize, usize, WF::Weight)> = self.verts()
+            .flat_map(|from| from.edges()
+                .zip(weights.of(from.id()))
+                .enumerate()
+                .filter(move |(_, ((_, to), _))| to.id() != from.id())
+                .map(move |(edge_idx, ((_, to), weight))| (from.id(), edge_idx, to.id(), *weight)))
+            .collect();
+        // stable, so ties keep the order of edges
+        edges.sort_by(|a, b| a.3.partial_cmp(&b.3).unwrap_or(Ordering::Equal));
+
+        let mut sets = DisjointSets::new(self.verts_cnt());
+        let mut forest = SpanningForest{ graph: self, edges: vec![], weight: WF::Weight::zero() };
+        for (from, edge_idx, to, weight) in edges {
+            if forest.edges.len() + 1 == self.verts_cnt() {
+                break
+            }
+            if sets.union(from, to) {
+                forest.edges.push((from, edge_idx));
+                forest.weight = forest.weight + weight;
+            }
+        }
+        Ok(forest)
+    }
+
+    /// Minimum spanning forest by Prim's algorithm, which grows a tree from the first verticle
+    /// of every component. Edge directions are ignored, loops are never chosen
+    pub fn prim<WF>(&self, weight: WF) -> ForestResult<'_, VD, EL, WF::Weight, WF::Error>
+        where WF: EdgeWeight<EL>,
+    {
+        let weights = self.edge_weights(weight)?;
+        // edges of every verticle in both directions as (neighbour, from, edge_idx, weight)
+        let mut incident = vec![vec![]; self.verts_cnt()];
+        self.verts()
+            .for_each(|from| from.edges()
+                .zip(weights.of(from.id()))
+                .enumerate()
+                .filter(|(_, ((_, to), _))| to.id() != from.id())
+                .for_each(|(edge_idx, ((_, to), weight))| {
+                    incident[from.id()].push((to.id(), from.id(), edge_idx, *weight));
+                    incident[to.id()].push((from.id(), from.id(), edge_idx, *weight));
+                }));
+
+        let mut in_tree = vec![false; self.verts_cnt()];
+        // the cheapest known edge connecting a verticle to the tree as (weight, from, edge_idx)
+        let mut cheapest: Vec<Option<(WF::Weight, usize, usize)>> = vec![None; self.verts_cnt()];
+        let mut forest = SpanningForest{ graph: self, edges: vec![], weight: WF::Weight::zero() };
+
+        for root in 0..self.verts_cnt() {
+            if in_tree[root] {
+                continue
+            }
+            let mut queue = BinaryHeap::from([MinFirst{ priority: WF::Weight::zero(), vert: root }]);
+
+            while let Some(MinFirst{ priority, vert }) = queue.pop() {
+                if in_tree[vert] {
+                    continue
+                }
+                if let Some((weight, from, edge_idx)) = cheapest[vert] {
+                    // a cheaper edge was found since then
+                    if weight < priority {
+                        continue
+                    }
+                    forest.edges.push((from, edge_idx));
+                    forest.weight = forest.weight + weight;
+                }
+                in_tree[vert] = true;
+
+                incident[vert].iter()
+                    .filter(|(next, ..)| !in_tree[*next])
+                    .for_each(|(next, from, edge_idx, weight)| {
+                        if cheapest[*next].is_none_or(|(best, ..)| *weight < best) {
+                            cheapest[*next] = Some((*weight, *from, *edge_idx));
+                            queue.push(MinFirst{ priority: *weight, vert: *next });
+                        }
+                    });
+            }
+        }
+        Ok(forest)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{read_tgf, ParseWeight};
+
+    use super::*;
+
+    fn cities() -> Graph<String, String>
+    {
+        let input = r"
+            1 A
+            2 B
+            3 C
+            4 D
+            5 E
+            6 X
+            7 Y
+            #
+            1 2 7
+            4 1 5
+            2 3 8
+            2 4 9
+            5 2 7
+            3 5 5
+            4 5 15
+            1 1 -100
+            6 7 -3
+            7 6 1
+        ";
+        read_tgf(input.lines())
+            .expect("input is valid")
+    }
+
+    #[test]
+    fn same_forest()
+    {
+        let graph = cities();
+        let kruskal = graph.kruskal(ParseWeight::<i32>::new())
+            .expect("weights are numbers");
+        let prim = graph.prim(ParseWeight::<i32>::new())
+            .expect("weights are numbers");
+
+        assert_eq!(kruskal.weight(), 24 - 3);
+        assert_eq!(prim.weight(), kruskal.weight());
+        assert_eq!(kruskal.trees_cnt(), 2);
+        assert_eq!(prim.trees_cnt(), 2);
+
+        let mut kruskal_edges = kruskal.edge_ids().to_vec();
+        let mut prim_edges = prim.edge_ids().to_vec();
+        kruskal_edges.sort();
+        prim_edges.sort();
+        // weights are distinct enough to make the tree unique
+        assert_eq!(kruskal_edges, prim_edges);
+
+        let labels: Vec<&str> = kruskal.edges()
+            .map(|(_, label, _)| label.as_str())
+            .collect();
+        assert_eq!(labels, ["-3", "5", "5", "7", "7"]);
+    }
+
+    #[test]
+    fn forest_graph()
+    {
+        let graph = cities();
+        let forest = graph.prim(ParseWeight::<f64>::new())
+            .expect("weights are numbers")
+            .to_graph();
+        assert_eq!(forest.verts_cnt(), graph.verts_cnt());
+
+        // the same components as in the original graph
+        let components = forest.weakly_connected_components();
+        assert_eq!(components, graph.weakly_connected_components());
+        assert_eq!(components.sizes(), [5, 2]);
+        assert!(forest.toposort().is_ok());
+
+        let edges: usize = forest.verts()
+            .map(|vert| vert.edges_cnt())
+            .sum();
+        assert_eq!(edges, 5);
+    }
+
+    #[test]
+    fn empty_and_isolated()
+    {
+        let mut graph = Graph::<(), u32>::new();
+        let forest = graph.kruskal(|label: &u32| *label)
+            .expect("closure can't fail");
+        assert_eq!((forest.weight(), forest.trees_cnt()), (0, 0));
+
+        graph.extend([(), ()]);
+        let forest = graph.prim(|label: &u32| *label)
+            .expect("closure can't fail");
+        assert_eq!((forest.weight(), forest.trees_cnt()), (0, 2));
+        assert_eq!(forest.edges().count(), 0);
+    }
+}
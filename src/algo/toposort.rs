@@ -0,0 +1,144 @@
+use std::{error::Error, fmt::{Debug, Display}};
+
+use crate::{DfsEvent, Graph, Path, VertHandle};
+
+/// Error of [Graph::toposort] holding one of the cycles of the graph
+#[derive(Debug)]
+pub struct CycleError<'gr, VD, EL>
+{
+    cycle: Path<'gr, VD, EL>,
+}
+
+impl<'gr, VD, EL> CycleError<'gr, VD, EL>
+{
+    /// Closed path, which starts and ends with the same verticle
+    pub fn cycle(&self) -> &Path<'gr, VD, EL>
+    { &self.cycle }
+
+    pub fn into_cycle(self) -> Path<'gr, VD, EL>
+    { self.cycle }
+}
+
+impl<VD, EL> Display for CycleError<'_, VD, EL>
+    where VD: Display,
+          EL: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.write_fmt(format_args!("Graph has a cycle: {}", **self.cycle.source()))?;
+        self.cycle.edges()
+            .try_for_each(|(_, label, to)| f.write_fmt(format_args!(" -[{}]-> {}", label, **to)))
+    }
+}
+
+impl<VD, EL> Error for CycleError<'_, VD, EL>
+    where VD: Debug + Display,
+          EL: Debug + Display,
+{}
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Orders verticles so every edge goes from an earlier verticle to a later one.
+    /// Fails with the first cycle found by [Graph::dfs], loops are cycles too
+    pub fn toposort(&self) -> Result<Vec<VertHandle<'_, VD, EL>>, CycleError<'_, VD, EL>>
+    {
+        let mut finished = Vec::with_capacity(self.verts_cnt());
+        // current DFS path along with labels of the tree edges
+        let mut path: Vec<(VertHandle<'_, VD, EL>, Option<&EL>)> = vec![];
+        let mut tree_label = None;
+
+        for event in self.dfs() {
+            match event {
+                DfsEvent::TreeEdge(_, label, _) => tree_label = Some(label),
+                DfsEvent::Discover(vert) => path.push((vert, tree_label.take())),
+                DfsEvent::Finish(vert) => {
+                    path.pop();
+                    finished.push(vert);
+                },
+                DfsEvent::BackEdge(_, label, to) => {
+                    let start = path.iter()
+                        .rposition(|(vert, _)| vert.id() == to.id())
+                        .expect("back edge leads to a verticle on the path");
+                    let mut cycle = Path::new(to);
+                    path[start + 1..].iter()
+                        .for_each(|(vert, label)| cycle.push(label.expect("only roots have no label"), *vert));
+                    cycle.push(label, to);
+                    return Err(CycleError{ cycle })
+                },
+                DfsEvent::ForwardEdge(..) | DfsEvent::CrossEdge(..) => (),
+            }
+        }
+
+        finished.reverse();
+        Ok(finished)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{test_util::months, read_tgf};
+
+    use super::*;
+
+    fn tasks(input: &str) -> Graph<String, String>
+    {
+        read_tgf(input.lines())
+            .expect("input is valid")
+    }
+
+    #[test]
+    fn order()
+    {
+        let graph = months();
+        let order = graph.toposort()
+            .expect("months are acyclic");
+        assert_eq!(order.len(), graph.verts_cnt());
+
+        let mut position = vec![0; graph.verts_cnt()];
+        order.iter()
+            .enumerate()
+            .for_each(|(pos, vert)| position[vert.id()] = pos);
+        graph.verts()
+            .for_each(|from| from.edges()
+                .for_each(|(_, to)| assert!(position[from.id()] < position[to.id()])));
+    }
+
+    #[test]
+    fn cycle()
+    {
+        let graph = tasks(r"
+            1 compile
+            2 link
+            3 test
+            4 generate
+            #
+            1 2 objects
+            2 3 binary
+            4 1 sources
+            3 4 fixtures
+        ");
+        let err = graph.toposort()
+            .expect_err("there is a cycle");
+        assert_eq!(err.cycle().ids(), [0, 1, 2, 3, 0]);
+        assert_eq!(err.cycle().labels(), [&"objects", &"binary", &"fixtures", &"sources"]);
+        assert_eq!(err.to_string(),
+            "Graph has a cycle: compile -[objects]-> link -[binary]-> test -[fixtures]-> generate -[sources]-> compile");
+    }
+
+    #[test]
+    fn loop_is_cycle()
+    {
+        let graph = tasks(r"
+            1 a
+            2 b
+            #
+            1 2
+            2 2 again
+        ");
+        let err = graph.toposort()
+            .expect_err("there is a loop");
+        assert_eq!(err.cycle().ids(), [1, 1]);
+        assert_eq!(err.to_string(), "Graph has a cycle: b -[again]-> b");
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::VecDeque;
+
+use crate::{EdgeIter, Graph, VertHandle};
+
+/// Decision of a [Visitor] on how to proceed with traversal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Control
+{
+    Continue,
+    /// For a verticle: don't look at its edges. For an edge: don't follow it
+    SkipChildren,
+    /// Stop the whole traversal
+    Stop,
+}
+
+/// Controls traversal made by [Graph::bfs_visit_from] or [Graph::dfs_visit_from].
+/// Both methods continue by default
+pub trait Visitor<'gr, VD, EL>
+{
+    /// Called once for every visited verticle before looking at its edges
+    fn visit_vert(&mut self, _vert: VertHandle<'gr, VD, EL>) -> Control
+    { Control::Continue }
+
+    /// Called for every edge of visited verticles, which are not skipped.
+    /// Edges leading to already visited verticles are reported too, following them does nothing
+    fn visit_edge(&mut self, _from: VertHandle<'gr, VD, EL>, _label: &'gr EL, _to: VertHandle<'gr, VD, EL>) -> Control
+    { Control::Continue }
+}
+
+/// [Visitor] calling closure on verticles, see [vert_visitor]
+#[derive(Debug, Clone, Copy)]
+pub struct VertVisitor<F>(F);
+
+/// Makes [Visitor] of a closure deciding on verticles and following all the edges
+pub fn vert_visitor<'gr, VD, EL, F>(visit: F) -> VertVisitor<F>
+    where F: FnMut(VertHandle<'gr, VD, EL>) -> Control,
+          VD: 'gr,
+          EL: 'gr,
+{ VertVisitor(visit) }
+
+impl<'gr, VD, EL, F> Visitor<'gr, VD, EL> for VertVisitor<F>
+    where F: FnMut(VertHandle<'gr, VD, EL>) -> Control,
+          VD: 'gr,
+          EL: 'gr,
+{
+    fn visit_vert(&mut self, vert: VertHandle<'gr, VD, EL>) -> Control
+    { (self.0)(vert) }
+}
+
+/// [Visitor] calling closure on edges, see [edge_visitor]
+#[derive(Debug, Clone, Copy)]
+pub struct EdgeVisitor<F>(F);
+
+/// Makes [Visitor] of a closure deciding on edges and looking at edges of all the visited verticles
+pub fn edge_visitor<'gr, VD, EL, F>(visit: F) -> EdgeVisitor<F>
+    where F: FnMut(VertHandle<'gr, VD, EL>, &'gr EL, VertHandle<'gr, VD, EL>) -> Control,
+          VD: 'gr,
+          EL: 'gr,
+{ EdgeVisitor(visit) }
+
+impl<'gr, VD, EL, F> Visitor<'gr, VD, EL> for EdgeVisitor<F>
+    where F: FnMut(VertHandle<'gr, VD, EL>, &'gr EL, VertHandle<'gr, VD, EL>) -> Control,
+          VD: 'gr,
+          EL: 'gr,
+{
+    fn visit_edge(&mut self, from: VertHandle<'gr, VD, EL>, label: &'gr EL, to: VertHandle<'gr, VD, EL>) -> Control
+    { (self.0)(from, label, to) }
+}
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Breadth-first traversal from `from` controlled by `visitor`. Unlike [Graph::bfs_from],
+    /// it doesn't go on to verticles unreachable from `from`.
+    /// Returns [Control::Stop] if the visitor stopped it, [Control::Continue] otherwise
+    pub fn bfs_visit_from<'gr, V>(&'gr self, from: VertHandle<'gr, VD, EL>, mut visitor: V) -> Control
+        where V: Visitor<'gr, VD, EL>,
+    {
+        assert!(from.is_of(self), "'from' is from  different owner");
+
+        let mut marked = vec![false; self.verts_cnt()];
+        marked[from.id()] = true;
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(vert) = queue.pop_front() {
+            match visitor.visit_vert(vert) {
+                Control::Continue => (),
+                Control::SkipChildren => continue,
+                Control::Stop => return Control::Stop,
+            }
+
+            for (label, to) in vert.edges() {
+                match visitor.visit_edge(vert, label, to) {
+                    Control::Continue if !marked[to.id()] => {
+                        marked[to.id()] = true;
+                        queue.push_back(to);
+                    },
+                    Control::Continue | Control::SkipChildren => (),
+                    Control::Stop => return Control::Stop,
+                }
+            }
+        }
+        Control::Continue
+    }
+
+    /// Depth-first traversal from `from` controlled by `visitor`. Only verticles reachable
+    /// from `from` are visited, in the order they are discovered by [Graph::dfs_from].
+    /// Returns [Control::Stop] if the visitor stopped it, [Control::Continue] otherwise
+    pub fn dfs_visit_from<'gr, V>(&'gr self, from: VertHandle<'gr, VD, EL>, mut visitor: V) -> Control
+        where V: Visitor<'gr, VD, EL>,
+    {
+        assert!(from.is_of(self), "'from' is from  different owner");
+
+        let mut marked = vec![false; self.verts_cnt()];
+        let mut stack = vec![];
+
+        if discover(from, &mut visitor, &mut marked, &mut stack) == Control::Stop {
+            return Control::Stop
+        }
+
+        while let Some((vert, edges)) = stack.last_mut() {
+            let vert = *vert;
+            let Some((label, to)) = edges.next() else {
+                stack.pop();
+                continue
+            };
+
+            match visitor.visit_edge(vert, label, to) {
+                Control::Continue if !marked[to.id()] => {
+                    if discover(to, &mut visitor, &mut marked, &mut stack) == Control::Stop {
+                        return Control::Stop
+                    }
+                },
+                Control::Continue | Control::SkipChildren => (),
+                Control::Stop => return Control::Stop,
+            }
+        }
+        Control::Continue
+    }
+}
+
+type DfsStack<'gr, VD, EL> = Vec<(VertHandle<'gr, VD, EL>, EdgeIter<'gr, VD, EL>)>;
+
+/// Visits verticle discovered by DFS and pushes it to the `stack` unless its children are skipped
+fn discover<'gr, VD, EL, V>(
+    vert: VertHandle<'gr, VD, EL>,
+    visitor: &mut V,
+    marked: &mut [bool],
+    stack: &mut DfsStack<'gr, VD, EL>) -> Control
+    where V: Visitor<'gr, VD, EL>,
+{
+    marked[vert.id()] = true;
+    let control = visitor.visit_vert(vert);
+    if control == Control::Continue {
+        stack.push((vert, vert.edges()));
+    }
+    control
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::test_util::{months, find};
+
+    use super::*;
+
+    #[test]
+    fn dont_walk_past_december()
+    {
+        let graph = months();
+        let september = find(&graph, "September");
+
+        let mut visited = vec![];
+        let res = graph.bfs_visit_from(september, vert_visitor(|vert: VertHandle<'_, String, String>| {
+            visited.push(vert.to_string());
+            when! {
+                vert.as_str() == "December" => Control::SkipChildren,
+                _ => Control::Continue,
+            }
+        }));
+        assert_eq!(res, Control::Continue);
+        // January and April are still reachable through June
+        assert_eq!(visited, ["September", "December", "June", "April", "January", "March"]);
+
+        let mut visited = vec![];
+        graph.dfs_visit_from(september, vert_visitor(|vert: VertHandle<'_, String, String>| {
+            visited.push(vert.to_string());
+            when! {
+                vert.as_str() == "December" || vert.as_str() == "June" => Control::SkipChildren,
+                _ => Control::Continue,
+            }
+        }));
+        assert_eq!(visited, ["September", "December", "June"]);
+    }
+
+    #[test]
+    fn skip_edges()
+    {
+        let graph = months();
+        let december = find(&graph, "December");
+
+        // don't celebrate
+        let no_holidays = || edge_visitor(|_, label: &String, _| when! {
+            label.is_empty() => Control::Continue,
+            _ => Control::SkipChildren,
+        });
+
+        // both edges of December are holidays
+        let mut bfs_cnt = (0, 0);
+        graph.bfs_visit_from(december, VisitCounter(&mut bfs_cnt, no_holidays()));
+        assert_eq!(bfs_cnt, (1, 2));
+
+        let mut dfs_cnt = (0, 0);
+        graph.dfs_visit_from(december, VisitCounter(&mut dfs_cnt, no_holidays()));
+        assert_eq!(dfs_cnt, (1, 2));
+    }
+
+    // counts visited verticles and edges
+    struct VisitCounter<'a, V>(&'a mut (usize, usize), V);
+
+    impl<'gr, VD, EL, V> Visitor<'gr, VD, EL> for VisitCounter<'_, V>
+        where V: Visitor<'gr, VD, EL>,
+    {
+        fn visit_vert(&mut self, vert: VertHandle<'gr, VD, EL>) -> Control
+        {
+            self.0.0 += 1;
+            self.1.visit_vert(vert)
+        }
+
+        fn visit_edge(&mut self, from: VertHandle<'gr, VD, EL>, label: &'gr EL, to: VertHandle<'gr, VD, EL>) -> Control
+        {
+            self.0.1 += 1;
+            self.1.visit_edge(from, label, to)
+        }
+    }
+
+    #[test]
+    fn stop()
+    {
+        let graph = months();
+        let september = find(&graph, "September");
+
+        let mut visited = 0;
+        let res = graph.dfs_visit_from(september, vert_visitor(|vert: VertHandle<'_, String, String>| {
+            visited += 1;
+            when! {
+                vert.as_str() == "January" => Control::Stop,
+                _ => Control::Continue,
+            }
+        }));
+        assert_eq!(res, Control::Stop);
+        // September -> December -> January
+        assert_eq!(visited, 3);
+
+        let mut edges = 0;
+        let res = graph.bfs_visit_from(september, edge_visitor(|_, _, _| {
+            edges += 1;
+            when! {
+                edges == 4 => Control::Stop,
+                _ => Control::Continue,
+            }
+        }));
+        assert_eq!(res, Control::Stop);
+        assert_eq!(edges, 4);
+    }
+}
@@ -0,0 +1,293 @@
+use crate::{EdgeWeight, Graph, VertHandle, Weight, WeightError, WeightErrorKind};
+
+/// What [RandomWalk] does at a verticle without edges to follow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DeadEnd
+{
+    /// The walk ends
+    #[default]
+    Stop,
+    /// The walk stays at the verticle forever
+    Stay,
+    /// The walk jumps back to its start
+    Restart,
+    /// The walk jumps to a uniformly chosen verticle of the graph
+    Teleport,
+}
+
+/// Endless random walk through a graph made by [Graph::random_walk].
+///
+/// The first yielded verticle is the start. The same seed, options and graph
+/// always produce the same walk, use [Iterator::take] to limit its length
+pub struct RandomWalk<'gr, VD, EL>
+{
+    graph: &'gr Graph<VD, EL>,
+    start: VertHandle<'gr, VD, EL>,
+    curr: Option<VertHandle<'gr, VD, EL>>,
+    rng: SplitMix64,
+    restart: f64,
+    dead_end: DeadEnd,
+    // cumulative weights of every verticle's edges, uniform choice if None
+    bias: Option<Vec<Vec<f64>>>,
+    finished: bool,
+}
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Random walk from `start` choosing edges uniformly, never restarting and stopping at dead ends
+    pub fn random_walk<'gr>(&'gr self, start: VertHandle<'gr, VD, EL>, seed: u64) -> RandomWalk<'gr, VD, EL>
+    {
+        assert!(start.is_of(self), "'start' is from  different owner");
+
+        RandomWalk {
+            graph: self,
+            start,
+            curr: None,
+            rng: SplitMix64(seed),
+            restart: 0.0,
+            dead_end: DeadEnd::default(),
+            bias: None,
+            finished: false,
+        }
+    }
+}
+
+impl<'gr, VD, EL> RandomWalk<'gr, VD, EL>
+{
+    /// Before every step the walk jumps back to its start with `probability`
+    pub fn restart_probability(mut self, probability: f64) -> Self
+    {
+        assert!((0.0..=1.0).contains(&probability), "probability should be within [0, 1]");
+        self.restart = probability;
+        self
+    }
+
+    pub fn dead_end(mut self, policy: DeadEnd) -> Self
+    {
+        self.dead_end = policy;
+        self
+    }
+
+    /// Chooses edges with probability proportional to their weights.
+    /// Weights, and their sums over edges of every verticle, have to be finite and non-negative.
+    /// Edges of zero weight are never followed, so a verticle having only such edges is a dead end
+    pub fn weighted<WF>(mut self, weight: WF) -> Result<Self, WeightError<WF::Error>>
+        where WF: EdgeWeight<EL>,
+    {
+        let weights = self.graph.non_negative_weights(weight)?;
+
+        let bias = (0..self.graph.verts_cnt())
+            .map(|from| weights.of(from)
+                .iter()
+                .enumerate()
+                .scan(0.0, |total, (edge_idx, w)| {
+                    *total += w.to_f64();
+                    // an infinite sum would make every later edge win
+                    Some(when! {
+                        total.is_finite() => Ok(*total),
+                        _ => Err(WeightError::new(from, edge_idx, WeightErrorKind::Infinite)),
+                    })
+                })
+                .collect::<Result<_, _>>())
+            .collect::<Result<_, _>>()?;
+        self.bias = Some(bias);
+        Ok(self)
+    }
+
+// private:
+    /// Follows a random edge of `from`, `None` if it's a dead end
+    fn follow(&mut self, from: VertHandle<'gr, VD, EL>) -> Option<VertHandle<'gr, VD, EL>>
+    {
+        let edge_idx = match &self.bias {
+            None if from.edges_cnt() == 0 => return None,
+            None => self.rng.below(from.edges_cnt()),
+            Some(bias) => {
+                let cumulative = &bias[from.id()];
+                let total = cumulative.last().copied()
+                    .unwrap_or(0.0);
+                if total <= 0.0 {
+                    return None
+                }
+                let point = self.rng.next_f64() * total;
+                // zero-weight edges don't extend the cumulative sum, so they can't be hit
+                cumulative.partition_point(|sum| *sum <= point)
+                    .min(cumulative.len() - 1)
+            },
+        };
+        from.edge(edge_idx)
+            .map(|(_, to)| to)
+    }
+}
+
+impl<'gr, VD, EL> Iterator for RandomWalk<'gr, VD, EL>
+{
+    type Item = VertHandle<'gr, VD, EL>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        if self.finished {
+            return None
+        }
+        let Some(curr) = self.curr else {
+            self.curr = Some(self.start);
+            return Some(self.start)
+        };
+
+        // the restart is drawn even when it's impossible to keep walks reproducible across probabilities
+        let next = when! {
+            self.rng.next_f64() < self.restart => Some(self.start),
+            _ => self.follow(curr),
+        };
+        let next = next.or_else(|| match self.dead_end {
+            DeadEnd::Stop => None,
+            DeadEnd::Stay => Some(curr),
+            DeadEnd::Restart => Some(self.start),
+            DeadEnd::Teleport => {
+                let id = self.rng.below(self.graph.verts_cnt());
+                self.graph.get_vert(id)
+            },
+        });
+
+        self.finished = next.is_none();
+        self.curr = next;
+        next
+    }
+}
+
+/// Small fast generator, good enough for sampling, see <https://prng.di.unimi.it/splitmix64.c>
+#[derive(Debug, Clone)]
+struct SplitMix64(u64);
+
+impl SplitMix64
+{
+    fn next_u64(&mut self) -> u64
+    {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    fn next_f64(&mut self) -> f64
+    { (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 }
+
+    /// Uniform in [0, bound)
+    fn below(&mut self, bound: usize) -> usize
+    { ((self.next_u64() as u128 * bound as u128) >> 64) as usize }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{test_util::{months, find}, try_weight, WeightErrorKind};
+
+    use super::*;
+
+    fn ids<'gr>(walk: impl Iterator<Item = VertHandle<'gr, String, String>>) -> Vec<usize>
+    { walk.map(|vert| vert.id()).collect() }
+
+    #[test]
+    fn reproducible()
+    {
+        let graph = months();
+        let september = find(&graph, "September");
+
+        let walk = |seed| ids(graph.random_walk(september, seed)
+            .restart_probability(0.3)
+            .take(50));
+        assert_eq!(walk(42), walk(42));
+        assert_ne!(walk(42), walk(43));
+
+        // every step follows an edge or restarts
+        let walk = walk(7);
+        assert_eq!(walk[0], september.id());
+        walk.windows(2)
+            .for_each(|pair| {
+                let from = graph.get_vert(pair[0]).expect("vert exists");
+                assert!(pair[1] == september.id() || from.edges().any(|(_, to)| to.id() == pair[1]));
+            });
+    }
+
+    #[test]
+    fn dead_ends()
+    {
+        let graph = months();
+        let may = find(&graph, "May");
+        let march = find(&graph, "March");
+
+        // May -> April -> March, which has no edges
+        let walk = ids(graph.random_walk(may, 0));
+        assert_eq!(walk, [may.id(), find(&graph, "April").id(), march.id()]);
+
+        let walk = ids(graph.random_walk(may, 0)
+            .dead_end(DeadEnd::Stay)
+            .take(5));
+        assert_eq!(walk[2..], [march.id(); 3]);
+
+        let walk = ids(graph.random_walk(may, 0)
+            .dead_end(DeadEnd::Restart)
+            .take(7));
+        assert_eq!(walk[3..], walk[..4]);
+
+        let walk = ids(graph.random_walk(march, 5)
+            .dead_end(DeadEnd::Teleport)
+            .take(100));
+        assert_eq!(walk.len(), 100);
+    }
+
+    #[test]
+    fn weighted()
+    {
+        let graph = months();
+        let december = find(&graph, "December");
+
+        // only holidays are worth following, and they lead to months with empty labels only
+        (0..10).for_each(|seed| {
+            let walk = ids(graph.random_walk(december, seed)
+                .weighted(|label: &String| label.len())
+                .expect("lengths are valid weights"));
+            assert_eq!(walk.len(), 2);
+            assert_ne!(walk[1], find(&graph, "June").id());
+        });
+
+        let april = graph.random_walk(december, 3)
+            .dead_end(DeadEnd::Restart)
+            .weighted(|label: &String| when! {
+                label.starts_with("April") => 3.0,
+                _ => 1.0,
+            })
+            .expect("weights are valid")
+            .take(4000)
+            .collect::<Vec<_>>()
+            .windows(2)
+            .filter(|pair| pair[0].id() == december.id())
+            .fold([0, 0], |mut counts, pair| {
+                counts[(pair[1].as_str() == "April") as usize] += 1;
+                counts
+            });
+        let ratio = april[1] as f64 / (april[0] + april[1]) as f64;
+        assert!((ratio - 0.75).abs() < 0.05, "ratio is {ratio}");
+
+        let err = graph.random_walk(december, 0)
+            .weighted(try_weight(|_: &String| Ok::<_, ()>(-1)))
+            .err()
+            .expect("negative weights are rejected");
+        assert!(matches!(err.kind(), WeightErrorKind::Negative));
+
+        let err = graph.random_walk(december, 0)
+            .weighted(|_: &String| f64::INFINITY)
+            .err()
+            .expect("infinite weights are rejected");
+        assert!(matches!(err.kind(), WeightErrorKind::Infinite));
+        assert_eq!((err.from(), err.edge_idx()), (0, 0));
+        // December is the first one with two edges
+        let err = graph.random_walk(december, 0)
+            .weighted(|_: &String| f64::MAX)
+            .err()
+            .expect("infinite sums are rejected");
+        assert!(matches!(err.kind(), WeightErrorKind::Infinite));
+        assert_eq!((err.from(), err.edge_idx()), (4, 1));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{Components, DisjointSets, Graph};
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Components of the graph with edge directions ignored.
+    /// Unlike [Graph::bfs], it tells which verticles belong together
+    pub fn weakly_connected_components(&self) -> Components
+    {
+        let mut sets = DisjointSets::new(self.verts_cnt());
+        self.verts()
+            .for_each(|from| from.edges()
+                .for_each(|(_, to)| {
+                    sets.union(from.id(), to.id());
+                }));
+
+        let labels: Vec<usize> = (0..self.verts_cnt())
+            .map(|vert| sets.find(vert))
+            .collect();
+        Components::from_labels(&labels)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::test_util::months;
+
+    #[test]
+    fn months_are_connected()
+    {
+        let mut graph = months();
+        let components = graph.weakly_connected_components();
+        // May only has an edge to April, so everything is connected
+        assert_eq!(components.count(), 1);
+        assert_eq!(components.sizes(), [7]);
+
+        graph.add_vert("Leap day".into());
+        graph.add_vert("Never".into());
+        graph.add_edge(7, 8, String::new())
+            .expect("verts exist");
+        let components = graph.weakly_connected_components();
+        assert_eq!(components.sizes(), [7, 2]);
+        assert_eq!(components.largest(), Some(0));
+        assert_eq!(components.members(1), Some(&[7, 8][..]));
+        assert!(!components.same(0, 8));
+    }
+
+    #[test]
+    fn ties_and_empty()
+    {
+        let mut graph = crate::Graph::<(), ()>::new();
+        assert_eq!(graph.weakly_connected_components().largest(), None);
+
+        graph.extend([(), (), (), ()]);
+        graph.add_edge(3, 2, ())
+            .and_then(|g| g.add_edge(1, 0, ()))
+            .expect("verts exist");
+        let components = graph.weakly_connected_components();
+        assert_eq!(components.sizes(), [2, 2]);
+        assert_eq!(components.largest(), Some(0));
+        assert_eq!(components.component_of(2), Some(1));
+    }
+}
@@ -0,0 +1,364 @@
+use std::{borrow::Borrow, convert::Infallible, error::Error, fmt::{Debug, Display}, marker::PhantomData, ops::{Add, Mul, Sub}, str::FromStr};
+
+use crate::Graph;
+
+/// Numeric cost of an edge
+pub trait Weight: Copy + PartialOrd + Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    fn zero() -> Self;
+
+    /// Used where weights are mixed with probabilities, e.g. by random walks
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_weight {
+    ($($t:ty),*) => {$(
+        impl Weight for $t
+        {
+            fn zero() -> Self
+            { 0 as $t }
+
+            fn to_f64(self) -> f64
+            { self as f64 }
+        }
+    )*};
+}
+
+impl_weight!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// Turns edge labels into [Weight]s for weighted algorithms.
+///
+/// Any closure `FnMut(&EL) -> W` is an infallible `EdgeWeight`.
+/// Use [try_weight] for fallible closures and [ParseWeight] to parse string labels,
+/// e.g. ones produced by [read_tgf](crate::read_tgf)
+pub trait EdgeWeight<EL>
+{
+    type Weight: Weight;
+    type Error;
+
+    fn weight(&mut self, label: &EL) -> Result<Self::Weight, Self::Error>;
+}
+
+impl<EL, W, F> EdgeWeight<EL> for F
+    where F: FnMut(&EL) -> W,
+          W: Weight,
+{
+    type Weight = W;
+    type Error = Infallible;
+
+    fn weight(&mut self, label: &EL) -> Result<W, Infallible>
+    { Ok(self(label)) }
+}
+
+/// [EdgeWeight] made by [try_weight]
+#[derive(Debug, Clone, Copy)]
+pub struct TryWeightFn<F>(F);
+
+/// Makes [EdgeWeight] of a fallible closure
+pub fn try_weight<EL, W, E, F>(weight: F) -> TryWeightFn<F>
+    where F: FnMut(&EL) -> Result<W, E>,
+          W: Weight,
+{ TryWeightFn(weight) }
+
+impl<EL, W, E, F> EdgeWeight<EL> for TryWeightFn<F>
+    where F: FnMut(&EL) -> Result<W, E>,
+          W: Weight,
+{
+    type Weight = W;
+    type Error = E;
+
+    fn weight(&mut self, label: &EL) -> Result<W, E>
+    { (self.0)(label) }
+}
+
+/// [EdgeWeight] parsing the whole label, with surrounding whitespace trimmed, as `W`
+pub struct ParseWeight<W>(PhantomData<fn() -> W>);
+
+impl<W> ParseWeight<W>
+{
+    pub fn new() -> Self
+    { ParseWeight(PhantomData) }
+}
+
+impl<W> Default for ParseWeight<W>
+{
+    fn default() -> Self
+    { ParseWeight::new() }
+}
+
+impl<W> Clone for ParseWeight<W>
+{
+    fn clone(&self) -> Self
+    { *self }
+}
+
+impl<W> Copy for ParseWeight<W> {}
+
+impl<W> Debug for ParseWeight<W>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    { f.write_str("ParseWeight") }
+}
+
+impl<EL, W> EdgeWeight<EL> for ParseWeight<W>
+    where EL: Borrow<str>,
+          W: Weight + FromStr,
+{
+    type Weight = W;
+    type Error = ParseWeightError<W::Err>;
+
+    fn weight(&mut self, label: &EL) -> Result<W, Self::Error>
+    {
+        let label: &str = label.borrow();
+        label.trim()
+            .parse()
+            .map_err(|error| ParseWeightError{ label: label.into(), error })
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseWeightError<E>
+{
+    label: String,
+    error: E,
+}
+
+impl<E> ParseWeightError<E>
+{
+    pub fn label(&self) -> &str
+    { &self.label }
+
+    pub fn error(&self) -> &E
+    { &self.error }
+}
+
+impl<E> Display for ParseWeightError<E>
+    where E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    { f.write_fmt(format_args!("Couldn't parse weight of '{}': {}", self.label, self.error)) }
+}
+
+impl<E> Error for ParseWeightError<E>
+    where E: Debug + Display,
+{}
+
+#[derive(Debug)]
+pub enum WeightErrorKind<E>
+{
+    /// [EdgeWeight] failed on the label
+    Label(E),
+    /// The algorithm requires non-negative weights
+    Negative,
+    /// Weight isn't comparable to itself, i.e. it's NaN
+    Incomparable,
+    /// The algorithm requires finite weights, or sums of them
+    Infinite,
+}
+
+impl<E> Display for WeightErrorKind<E>
+    where E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        use WeightErrorKind::*;
+        match self {
+            Label(err) => Display::fmt(err, f),
+            Negative => f.write_str("Negative weight is not allowed"),
+            Incomparable => f.write_str("Weight is not comparable"),
+            Infinite => f.write_str("Weight is infinite"),
+        }
+    }
+}
+
+/// Error of weighing an edge, which is `edge_idx`-th edge going from `from`
+#[derive(Debug)]
+pub struct WeightError<E>
+{
+    from: usize,
+    edge_idx: usize,
+    kind: WeightErrorKind<E>,
+}
+
+impl<E> WeightError<E>
+{
+    pub (crate) fn new(from: usize, edge_idx: usize, kind: WeightErrorKind<E>) -> Self
+    { WeightError{ from, edge_idx, kind } }
+
+    pub fn from(&self) -> usize
+    { self.from }
+
+    pub fn edge_idx(&self) -> usize
+    { self.edge_idx }
+
+    pub fn kind(&self) -> &WeightErrorKind<E>
+    { &self.kind }
+
+    pub fn into_kind(self) -> WeightErrorKind<E>
+    { self.kind }
+}
+
+impl<E> Display for WeightError<E>
+    where E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    { f.write_fmt(format_args!("On edge #{} from {}: {}", self.edge_idx, self.from, self.kind)) }
+}
+
+impl<E> Error for WeightError<E>
+    where E: Debug + Display,
+{}
+
+/// Result of algorithms, which weigh edges by [EdgeWeight] with error `E`
+pub type WeightResult<T, E> = Result<T, WeightError<E>>;
+
+/// Weights of all the edges of a graph, see [Graph::edge_weights]
+#[derive(Debug, Clone)]
+pub struct EdgeWeights<W>
+{
+    // weights of edges of every verticle in the same order as VertHandle::edges
+    pub (crate) weights: Vec<Vec<W>>,
+}
+
+impl<W> EdgeWeights<W>
+    where W: Weight,
+{
+    /// Weight of `edge_idx`-th edge going from `from`
+    pub fn get(&self, from: usize, edge_idx: usize) -> Option<W>
+    {
+        self.weights.get(from)
+            .and_then(|weights| weights.get(edge_idx))
+            .copied()
+    }
+
+    /// Weights of edges going from `from` in the same order as [VertHandle::edges](crate::VertHandle::edges)
+    ///
+    /// # Panics
+    /// If there is no verticle `from`, use [EdgeWeights::get] for unchecked ids
+    pub fn of(&self, from: usize) -> &[W]
+    { &self.weights[from] }
+
+    /// Returns the first negative weight as `(from, edge_idx)`
+    pub fn find_negative(&self) -> Option<(usize, usize)>
+    {
+        self.weights.iter()
+            .enumerate()
+            .find_map(|(from, weights)| weights.iter()
+                .position(|w| *w < W::zero())
+                .map(|edge_idx| (from, edge_idx)))
+    }
+}
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Weighs every edge of the graph once, stops on the first failure.
+    /// Weights that are not comparable to themselves (NaN) are rejected
+    pub fn edge_weights<WF>(&self, mut weight: WF) -> Result<EdgeWeights<WF::Weight>, WeightError<WF::Error>>
+        where WF: EdgeWeight<EL>,
+    {
+        let weights = self.verts()
+            .map(|from| from.edges()
+                .enumerate()
+                .map(|(edge_idx, (label, _))| {
+                    let error = |kind| WeightError::new(from.id(), edge_idx, kind);
+                    let w = weight.weight(label)
+                        .map_err(|err| error(WeightErrorKind::Label(err)))?;
+                    when! {
+                        w.partial_cmp(&w).is_none() => Err(error(WeightErrorKind::Incomparable)),
+                        _ => Ok(w),
+                    }
+                })
+                .collect())
+            .collect::<Result<_, _>>()?;
+        Ok(EdgeWeights{ weights })
+    }
+
+    /// Same as [Graph::edge_weights], but negative weights are rejected too
+    pub (crate) fn non_negative_weights<WF>(&self, weight: WF) -> WeightResult<EdgeWeights<WF::Weight>, WF::Error>
+        where WF: EdgeWeight<EL>,
+    {
+        let weights = self.edge_weights(weight)?;
+        match weights.find_negative() {
+            Some((from, edge_idx)) => Err(WeightError::new(from, edge_idx, WeightErrorKind::Negative)),
+            None => Ok(weights),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::{test_util::months, read_tgf};
+
+    #[test]
+    fn closures()
+    {
+        let mut graph = Graph::<(), &'static str>::new();
+        graph.extend([(), ()]);
+        graph.add_edge(0, 1, "four")
+            .and_then(|g| g.add_edge(1, 0, "two"))
+            .expect("verts exist");
+
+        let weights = graph.edge_weights(|label: &&str| label.len())
+            .expect("closure can't fail");
+        assert_eq!(weights.get(0, 0), Some(4));
+        assert_eq!(weights.of(1), [3]);
+        assert_eq!(weights.get(1, 1), None);
+
+        let err = graph.edge_weights(try_weight(|label: &&str| match *label {
+                "four" => Ok(4),
+                _ => Err("unknown number"),
+            }))
+            .expect_err("'two' is unknown");
+        assert_eq!((err.from(), err.edge_idx()), (1, 0));
+        assert!(matches!(err.kind(), WeightErrorKind::Label("unknown number")));
+    }
+
+    #[test]
+    fn parse_tgf_labels()
+    {
+        let input = r"
+            1 one
+            2 two
+            #
+            1 2 3
+            2 1  -2
+        ";
+        let graph = read_tgf(input.lines())
+            .expect("input is valid");
+
+        let weights = graph.edge_weights(ParseWeight::<f64>::new())
+            .expect("labels are numbers");
+        assert_eq!(weights.get(1, 0), Some(-2.0));
+        assert_eq!(weights.find_negative(), Some((1, 0)));
+
+        let err = graph.edge_weights(ParseWeight::<u32>::new())
+            .expect_err("-2 is not u32");
+        assert_eq!(err.to_string(), "On edge #0 from 1: Couldn't parse weight of '-2': invalid digit found in string");
+    }
+
+    #[test]
+    fn months_are_not_numbers()
+    {
+        let graph = months();
+        let err = graph.edge_weights(ParseWeight::<i64>::new())
+            .expect_err("labels are not numbers");
+        match err.into_kind() {
+            WeightErrorKind::Label(err) => assert_eq!(err.label(), ""),
+            kind => panic!("Unexpected error: {kind}"),
+        }
+    }
+
+    #[test]
+    fn nan_is_rejected()
+    {
+        let mut graph = Graph::<(), f64>::new();
+        graph.add_vert(());
+        graph.add_edge(0, 0, f64::NAN)
+            .expect("vert exists");
+        let err = graph.edge_weights(|label: &f64| *label)
+            .expect_err("NaN is not comparable");
+        assert!(matches!(err.kind(), WeightErrorKind::Incomparable));
+    }
+}
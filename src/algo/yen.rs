@@ -0,0 +1,234 @@
+use std::{cmp::Ordering, collections::HashSet};
+
+use crate::{EdgeWeight, EdgeWeights, Graph, Path, ShortestPaths, VertHandle, Weight, WeightResult};
+
+/// Path as ids: the verticles and edges between them as `(from, edge_idx)`
+struct Route<W>
+{
+    verts: Vec<usize>,
+    edges: Vec<(usize, usize)>,
+    cost: W,
+}
+
+type RoutesResult<'gr, VD, EL, W, E> = WeightResult<Vec<(W, Path<'gr, VD, EL>)>, E>;
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Up to `k` cheapest paths from `from` to `to` without repeated verticles, found by Yen's algorithm.
+    /// Paths are sorted by cost, the ones of equal cost by the number of edges.
+    /// Parallel edges make different paths. Negative weights are rejected
+    pub fn k_shortest_paths<'gr, WF>(&'gr self, from: VertHandle<'gr, VD, EL>, to: VertHandle<'gr, VD, EL>, k: usize, weight: WF)
+        -> RoutesResult<'gr, VD, EL, WF::Weight, WF::Error>
+        where WF: EdgeWeight<EL>,
+    {
+        assert!(from.is_of(self), "'from' is from  different owner");
+        assert!(to.is_of(self), "'to' is from  different owner");
+
+        let weights = self.non_negative_weights(weight)?;
+        let mut found: Vec<Route<WF::Weight>> = vec![];
+        let mut candidates: Vec<Route<WF::Weight>> = self.route(from.id(), to.id(), &weights, |_, _, _| true)
+            .into_iter()
+            .collect();
+        let mut seen: HashSet<Vec<(usize, usize)>> = candidates.iter()
+            .map(|route| route.edges.clone())
+            .collect();
+
+        while found.len() < k {
+            let Some(next) = (0..candidates.len()).min_by(|a, b| cheaper(&candidates[*a], &candidates[*b])) else {
+                break
+            };
+            found.push(candidates.swap_remove(next));
+            let last = found.last().expect("route was just pushed");
+
+            // deviate from the last route at every its verticle
+            for spur_idx in 0..last.edges.len() {
+                let root = &last.edges[..spur_idx];
+                let spur = last.verts[spur_idx];
+
+                // edges, which make already found routes with the same root
+                let taken: HashSet<(usize, usize)> = found.iter()
+                    .filter(|route| route.edges.len() > spur_idx && route.edges[..spur_idx] == *root)
+                    .map(|route| route.edges[spur_idx])
+                    .collect();
+                // the root can't be visited again
+                let mut on_root = vec![false; self.verts_cnt()];
+                last.verts[..spur_idx].iter()
+                    .for_each(|vert| on_root[*vert] = true);
+
+                let Some(spur_route) = self.route(spur, to.id(), &weights, |from, edge_idx, to| {
+                    !on_root[to] && !taken.contains(&(from, edge_idx))
+                }) else {
+                    continue
+                };
+
+                let cost = root.iter()
+                    .map(|(from, edge_idx)| weights.get(*from, *edge_idx).expect("every edge is weighed"))
+                    .fold(spur_route.cost, |total, weight| total + weight);
+                let route = Route {
+                    verts: last.verts[..spur_idx].iter().chain(&spur_route.verts).copied().collect(),
+                    edges: root.iter().chain(&spur_route.edges).copied().collect(),
+                    cost,
+                };
+                if seen.insert(route.edges.clone()) {
+                    candidates.push(route);
+                }
+            }
+        }
+
+        Ok(found.into_iter()
+            .map(|route| {
+                let start = self.get_vert(route.verts[0])
+                    .expect("route goes through valid verticles");
+                let path = route.edges.iter()
+                    .fold(Path::new(start), |mut path, (_, edge_idx)| {
+                        let (label, to) = path.target()
+                            .edge(*edge_idx)
+                            .expect("route goes through valid edges");
+                        path.push(label, to);
+                        path
+                    });
+                (route.cost, path)
+            })
+            .collect())
+    }
+
+    /// The cheapest route through `allowed` edges
+    fn route<W, F>(&self, from: usize, to: usize, weights: &EdgeWeights<W>, allowed: F) -> Option<Route<W>>
+        where W: Weight,
+              F: FnMut(usize, usize, usize) -> bool,
+    {
+        let ShortestPaths{ distance, parent, .. } = self.dijkstra_through(from, weights, Some(to), allowed);
+        let cost = distance[to]?;
+
+        let mut verts = vec![to];
+        let mut edges = vec![];
+        while let Some((prev, edge_idx)) = parent[verts[verts.len() - 1]] {
+            edges.push((prev, edge_idx));
+            verts.push(prev);
+        }
+        verts.reverse();
+        edges.reverse();
+        Some(Route{ verts, edges, cost })
+    }
+}
+
+fn cheaper<W: Weight>(fst: &Route<W>, snd: &Route<W>) -> Ordering
+{
+    fst.cost.partial_cmp(&snd.cost)
+        .unwrap_or(Ordering::Equal)
+        .then(fst.edges.len().cmp(&snd.edges.len()))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{read_tgf, ParseWeight};
+
+    use super::*;
+
+    // the classic example from Yen's algorithm description
+    fn roads() -> Graph<String, String>
+    {
+        let input = r"
+            1 C
+            2 D
+            3 E
+            4 F
+            5 G
+            6 H
+            #
+            1 2 3
+            1 3 2
+            2 4 4
+            3 2 1
+            3 4 2
+            3 5 3
+            4 5 2
+            4 6 1
+            5 6 2
+        ";
+        read_tgf(input.lines())
+            .expect("input is valid")
+    }
+
+    fn names(path: &Path<'_, String, String>) -> String
+    {
+        path.verts()
+            .iter()
+            .map(|vert| vert.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn three_shortest()
+    {
+        let graph = roads();
+        let c = graph.get_vert(0).expect("vert exists");
+        let h = graph.get_vert(5).expect("vert exists");
+
+        let paths = graph.k_shortest_paths(c, h, 3, ParseWeight::<u32>::new())
+            .expect("weights are numbers");
+        let found: Vec<(u32, String)> = paths.iter()
+            .map(|(cost, path)| (*cost, names(path)))
+            .collect();
+        assert_eq!(found, [(5, "CEFH".into()), (7, "CEGH".into()), (8, "CDFH".into())]);
+
+        let (_, path) = &paths[1];
+        assert_eq!(path.labels(), [&"2", &"3", &"2"]);
+    }
+
+    #[test]
+    fn all_simple_paths()
+    {
+        let graph = roads();
+        let c = graph.get_vert(0).expect("vert exists");
+        let h = graph.get_vert(5).expect("vert exists");
+
+        let paths = graph.k_shortest_paths(c, h, 100, ParseWeight::<u32>::new())
+            .expect("weights are numbers");
+        // C -> {D, E -> D, E} -> F -> {H, G -> H} and C -> E -> G -> H
+        assert_eq!(paths.len(), 7);
+        assert!(paths.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        paths.iter()
+            .for_each(|(cost, path)| {
+                let mut ids = path.ids();
+                ids.sort();
+                ids.dedup();
+                assert_eq!(ids.len(), path.verts().len(), "{} has a loop", names(path));
+                let total: u32 = path.labels()
+                    .iter()
+                    .map(|label| label.parse::<u32>().expect("label is a number"))
+                    .sum();
+                assert_eq!(total, *cost);
+            });
+    }
+
+    #[test]
+    fn parallel_edges()
+    {
+        let mut graph = Graph::<(), u8>::new();
+        graph.extend([(), (), ()]);
+        graph.add_edge(0, 1, 1)
+            .and_then(|g| g.add_edge(0, 1, 2))
+            .and_then(|g| g.add_edge(1, 2, 1))
+            .and_then(|g| g.add_edge(2, 0, 1))
+            .expect("verts exist");
+        let start = graph.get_vert(0).expect("vert exists");
+        let end = graph.get_vert(2).expect("vert exists");
+
+        let costs: Vec<u8> = graph.k_shortest_paths(start, end, 5, |label: &u8| *label)
+            .expect("closure can't fail")
+            .into_iter()
+            .map(|(cost, _)| cost)
+            .collect();
+        assert_eq!(costs, [2, 3]);
+
+        assert!(graph.k_shortest_paths(end, start, 0, |label: &u8| *label)
+            .expect("closure can't fail")
+            .is_empty());
+        let to_itself = graph.k_shortest_paths(start, start, 3, |label: &u8| *label)
+            .expect("closure can't fail");
+        assert_eq!(to_itself.len(), 1);
+        assert!(to_itself[0].1.is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+use std::{mem, sync::{Mutex, RwLock, PoisonError}};
+
+use super::{Graph, Snapshot};
+
+/// [Graph] shared between threads, where any number of readers work alongside a single writer.
+///
+/// Readers never see the graph itself, but [Snapshot]s published by the writer:
+/// * every snapshot is a state of the graph between two [ConcurrentGraph::write] calls,
+///   so readers never see a half-done write;
+/// * once `write` returns, every following [ConcurrentGraph::read] sees its result or a newer one,
+///   so reads made by one thread never go back in time;
+/// * a snapshot stays unchanged while it's held, so long reads (e.g. a whole [Graph::bfs])
+///   are consistent, but they don't see writes made meanwhile.
+///
+/// Reading only takes a read lock to clone a pointer to the latest snapshot, and publishing
+/// takes the write lock just to swap that pointer. So readers never wait for the writer mutating
+/// the graph, and the writer waits only for snapshot clones in flight, never for readers
+/// still traversing an older snapshot. Writers are serialized with each other.
+pub struct ConcurrentGraph<VD, EL>
+{
+    graph: Mutex<Graph<VD, EL>>,
+    published: RwLock<Snapshot<VD, EL>>,
+}
+
+impl<VD, EL> ConcurrentGraph<VD, EL>
+    where VD: Clone,
+          EL: Clone,
+{
+    pub fn new(graph: Graph<VD, EL>) -> Self
+    {
+        let published = RwLock::new(graph.snapshot());
+        ConcurrentGraph{ graph: Mutex::new(graph), published }
+    }
+
+    /// The latest published state of the graph
+    pub fn read(&self) -> Snapshot<VD, EL>
+    {
+        self.published.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Mutates the graph and publishes the result once `writer` returns.
+    ///
+    /// The published snapshot shares storage with the graph, so the next write copies
+    /// the table of chunks of verticles: every write takes O(V / 64) time on top of what `writer` does,
+    /// plus a copy of 64 pointers for every chunk it changes, see [Graph::snapshot].
+    /// Batch many small mutations into a single write, where possible.
+    ///
+    /// # Panics
+    /// If a previous `writer` panicked, as the graph might have been left half-modified.
+    /// Readers still see the last published state then.
+    pub fn write<T, F>(&self, writer: F) -> T
+        where F: FnOnce(&mut Graph<VD, EL>) -> T
+    {
+        let mut graph = self.graph.lock()
+            .expect("previous writer panicked");
+        let res = writer(&mut graph);
+
+        let snapshot = graph.snapshot();
+        let old = mem::replace(&mut *self.published.write().unwrap_or_else(PoisonError::into_inner), snapshot);
+        // the lock is already released, readers don't wait for the old snapshot to be dropped
+        drop(old);
+        res
+    }
+
+    pub fn into_inner(self) -> Graph<VD, EL>
+    {
+        self.graph.into_inner()
+            .expect("previous writer panicked")
+    }
+}
+
+impl<VD, EL> From<Graph<VD, EL>> for ConcurrentGraph<VD, EL>
+    where VD: Clone,
+          EL: Clone,
+{
+    fn from(graph: Graph<VD, EL>) -> Self
+    { ConcurrentGraph::new(graph) }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::{thread, sync::atomic::{AtomicBool, Ordering}};
+
+    use super::*;
+
+    fn assert_shareable<T: Send + Sync>() {}
+
+    #[test]
+    fn is_shareable()
+    {
+        assert_shareable::<Graph<String, String>>();
+        assert_shareable::<Snapshot<String, String>>();
+        assert_shareable::<ConcurrentGraph<String, String>>();
+    }
+
+    #[test]
+    fn read_your_writes()
+    {
+        let graph = ConcurrentGraph::new(Graph::<usize, ()>::new());
+        let old = graph.read();
+
+        let id = graph.write(|g| g.add_vert(10).id());
+        assert_eq!(id, 0);
+        assert_eq!(old.verts_cnt(), 0);
+        assert_eq!(graph.read().verts_cnt(), 1);
+
+        let graph = graph.into_inner();
+        assert_eq!(**graph.get_vert(0).expect("vert exists"), 10);
+    }
+
+    /// Writer grows a chain 0 -> 1 -> ... adding a verticle with an edge to it at once,
+    /// while readers check they never see a broken chain
+    #[test]
+    fn stress_readers_and_writer()
+    {
+        const VERTS: usize = 20_000;
+        const READERS: usize = 8;
+
+        let mut start = Graph::<usize, usize>::new();
+        start.add_vert(0);
+        let graph = ConcurrentGraph::new(start);
+        let done = AtomicBool::new(false);
+
+        thread::scope(|scope| {
+            let readers: Vec<_> = (0..READERS)
+                .map(|_| scope.spawn(|| {
+                    let mut last_seen = 0;
+                    loop {
+                        // read made after all the writes must see all of them
+                        let finished = done.load(Ordering::Acquire);
+                        let snapshot = graph.read();
+                        let start = snapshot.get_vert(0)
+                            .expect("first verticle is always there");
+
+                        let mut visited = 0;
+                        snapshot.bfs_from(start)
+                            .for_each(|vert| {
+                                assert_eq!(**vert, vert.id());
+                                assert_eq!(vert.edges_cnt(), usize::from(vert.id() + 1 < snapshot.verts_cnt()));
+                                vert.edges()
+                                    .for_each(|(label, to)| assert_eq!(*label, to.id()));
+                                visited += 1;
+                            });
+                        assert_eq!(visited, snapshot.verts_cnt());
+                        assert!(visited >= last_seen, "went back from {last_seen} to {visited} verts");
+
+                        last_seen = visited;
+                        if finished {
+                            break last_seen
+                        }
+                    }
+                }))
+                .collect();
+
+            (1..VERTS).for_each(|id| graph.write(|g| {
+                g.add_vert(id);
+                g.add_edge(id - 1, id, id)
+                    .expect("previous vert exists");
+            }));
+            done.store(true, Ordering::Release);
+
+            readers.into_iter()
+                .map(|reader| reader.join().expect("reader panicked"))
+                .for_each(|last_seen| assert_eq!(last_seen, VERTS));
+        });
+    }
+
+    #[test]
+    fn snapshots_outlive_writes()
+    {
+        let graph = ConcurrentGraph::new(Graph::<usize, ()>::new());
+        let snapshots: Vec<_> = (0..100)
+            .map(|id| {
+                graph.write(|g| g.add_vert(id).id());
+                graph.read()
+            })
+            .collect();
+
+        snapshots.iter()
+            .enumerate()
+            .for_each(|(id, snapshot)| {
+                assert_eq!(snapshot.verts_cnt(), id + 1);
+                assert!(snapshot.verts().all(|vert| **vert == vert.id()));
+            });
+    }
+}
@@ -1,5 +1,5 @@
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Edge<EL>
 {
     pub (crate) to: usize,
@@ -12,6 +12,9 @@ impl<EL> Edge<EL>
     pub (crate) fn new(to: usize, label: EL) -> Self
     { Edge{ to, label } }
 
+    pub (crate) fn replace_label(&mut self, label: EL) -> EL
+    { std::mem::replace(&mut self.label, label) }
+
     pub fn label(&self) -> &EL
     { &self.label }
 
@@ -0,0 +1,342 @@
+use std::{mem, sync::atomic::{AtomicUsize, Ordering}};
+
+use super::{Edge, Graph, GraphEvent, Vert};
+
+/// Single reversible mutation of a [Graph]. Reverting a change produces
+/// the change, which reverts it back, so the same type serves both undo and redo
+pub (crate) enum Change<VD, EL>
+{
+    /// the last verticle was added
+    InsertVert(usize),
+    /// the last verticle was removed
+    RemoveVert(Vert<VD, EL>),
+    InsertEdge{ from: usize, idx: usize },
+    RemoveEdge{ from: usize, idx: usize, edge: Edge<EL> },
+    /// data of the verticle was replaced, the previous one is stored
+    Data{ id: usize, data: VD },
+    /// label of the edge was replaced, the previous one is stored
+    Label{ from: usize, idx: usize, label: EL },
+}
+
+impl<VD, EL> Change<VD, EL>
+{
+    pub (crate) fn event(&self) -> GraphEvent
+    {
+        use Change::*;
+        match self {
+            InsertVert(id) => GraphEvent::VertAdded(*id),
+            RemoveVert(vert) => GraphEvent::VertRemoved(vert.id),
+            InsertEdge{ from, idx } => GraphEvent::EdgeAdded{ from: *from, edge_idx: *idx },
+            RemoveEdge{ from, idx, .. } => GraphEvent::EdgeRemoved{ from: *from, edge_idx: *idx },
+            Data{ id, .. } => GraphEvent::DataChanged(*id),
+            Label{ from, idx, .. } => GraphEvent::LabelChanged{ from: *from, edge_idx: *idx },
+        }
+    }
+}
+
+/// Position in the history of a journaled [Graph], see [Graph::checkpoint]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint
+{
+    session: usize,
+    depth: usize,
+    stamp: usize,
+}
+
+// every journal has its own session, so checkpoints can't be mistaken for ones
+// of the histories recorded before the journal was enabled again, or of other graphs
+static NEXT_SESSION: AtomicUsize = AtomicUsize::new(0);
+
+pub (crate) struct Journal<VD, EL>
+{
+    session: usize,
+    // every change is stamped, so checkpoints can tell if the history was rewritten
+    done: Vec<(usize, Change<VD, EL>)>,
+    undone: Vec<(usize, Change<VD, EL>)>,
+    next_stamp: usize,
+    save_data: fn(&VD) -> VD,
+}
+
+impl<VD, EL> Journal<VD, EL>
+{
+    fn new(save_data: fn(&VD) -> VD) -> Self
+    {
+        let session = NEXT_SESSION.fetch_add(1, Ordering::Relaxed);
+        Journal{ session, done: vec![], undone: vec![], next_stamp: 1, save_data }
+    }
+
+    fn push(&mut self, change: Change<VD, EL>)
+    {
+        self.undone.clear();
+        self.done.push((self.next_stamp, change));
+        self.next_stamp += 1;
+    }
+
+    fn checkpoint(&self) -> Checkpoint
+    {
+        let stamp = self.done.last()
+            .map(|(stamp, _)| *stamp)
+            .unwrap_or(0);
+        Checkpoint{ session: self.session, depth: self.done.len(), stamp }
+    }
+
+    fn reaches(&self, checkpoint: Checkpoint) -> bool
+    {
+        if checkpoint.session != self.session {
+            return false
+        }
+        match checkpoint.depth {
+            0 => true,
+            depth => self.done.get(depth - 1)
+                .is_some_and(|(stamp, _)| *stamp == checkpoint.stamp),
+        }
+    }
+}
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Starts recording mutations of the graph, so they can be undone.
+    ///
+    /// Recorded are added verticles and edges, changed labels, removed edges
+    /// and data changes made through [VertHandleMut](super::VertHandleMut). The latter saves
+    /// a copy of the data the first time a handle is mutably dereferenced.
+    /// Verticles can't be removed from a graph, so only undoing their addition removes them.
+    /// Does nothing if the journal is already enabled.
+    pub fn enable_journal(&mut self)
+        where VD: Clone,
+    {
+        if self.journal.is_none() {
+            self.journal = Some(Journal::new(VD::clone));
+        }
+    }
+
+    /// Stops recording mutations and forgets the recorded history
+    pub fn disable_journal(&mut self)
+    { self.journal = None }
+
+    pub fn is_journaled(&self) -> bool
+    { self.journal.is_some() }
+
+    /// Reverts the last recorded mutation, observers are notified about the reverting one.
+    /// Returns `false` if there is nothing to undo
+    pub fn undo(&mut self) -> bool
+    {
+        let Some((stamp, change)) = self.journal.as_mut().and_then(|j| j.done.pop()) else {
+            return false
+        };
+        let redo = self.revert(change);
+        self.notify(redo.event());
+        if let Some(journal) = self.journal.as_mut() {
+            journal.undone.push((stamp, redo));
+        }
+        true
+    }
+
+    /// Applies the last undone mutation again. Returns `false` if there is nothing to redo.
+    /// Any new mutation makes undone ones unreachable
+    pub fn redo(&mut self) -> bool
+    {
+        let Some((stamp, change)) = self.journal.as_mut().and_then(|j| j.undone.pop()) else {
+            return false
+        };
+        let undo = self.revert(change);
+        self.notify(undo.event());
+        if let Some(journal) = self.journal.as_mut() {
+            journal.done.push((stamp, undo));
+        }
+        true
+    }
+
+    /// Remembers current state of the graph to return to it with [Graph::rollback_to].
+    /// Returns `None` if the journal is disabled
+    pub fn checkpoint(&self) -> Option<Checkpoint>
+    {
+        self.journal.as_ref()
+            .map(Journal::checkpoint)
+    }
+
+    /// Undoes mutations until the graph returns to `checkpoint`. Returns `false`
+    /// and does nothing if the checkpoint is not reachable by undoing: it was made
+    /// before the journal was last enabled or by another graph, or the history after it
+    /// was undone and then overwritten
+    pub fn rollback_to(&mut self, checkpoint: Checkpoint) -> bool
+    {
+        let reachable = self.journal.as_ref()
+            .is_some_and(|journal| journal.reaches(checkpoint));
+        if reachable {
+            while self.journal.as_ref().is_some_and(|j| j.done.len() > checkpoint.depth) {
+                self.undo();
+            }
+        }
+        reachable
+    }
+
+    /// Notifies observers about `change` and saves it to the journal
+    pub (crate) fn record(&mut self, change: Change<VD, EL>)
+    {
+        self.notify(change.event());
+        if let Some(journal) = self.journal.as_mut() {
+            journal.push(change)
+        }
+    }
+
+    /// Saves data of the verticle `id` before it's changed in place
+    pub (crate) fn record_data(&mut self, id: usize)
+    {
+        if let Some(journal) = self.journal.as_mut() {
+            let data = (journal.save_data)(&self.verts[id]);
+            journal.push(Change::Data{ id, data })
+        }
+    }
+
+    /// Reverts `change` without recording it or notifying observers, returns the change reverting it back
+    pub (crate) fn revert(&mut self, change: Change<VD, EL>) -> Change<VD, EL>
+    {
+        use Change::*;
+        match change {
+            InsertVert(_) => {
+                let vert = self.verts.pop()
+                    .expect("inserted verticle must be the last one");
+                RemoveVert(vert)
+            },
+            RemoveVert(vert) => {
+                let id = vert.id;
+                self.verts.push(vert);
+                InsertVert(id)
+            },
+            InsertEdge{ from, idx } => {
+                let edge = self.verts[from].edges.remove(idx);
+                RemoveEdge{ from, idx, edge }
+            },
+            RemoveEdge{ from, idx, edge } => {
+                self.verts[from].edges.insert(idx, edge);
+                InsertEdge{ from, idx }
+            },
+            Data{ id, mut data } => {
+                mem::swap(&mut *self.verts[id], &mut data);
+                Data{ id, data }
+            },
+            Label{ from, idx, label } => {
+                let label = self.verts[from].edges[idx].replace_label(label);
+                Label{ from, idx, label }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{test_util::edges_of, Graph};
+
+    #[test]
+    fn undo_redo_edges()
+    {
+        let mut graph = Graph::<&'static str, u32>::new();
+        graph.enable_journal();
+        graph.extend(["a", "b", "c"]);
+        graph.add_edge(0, 1, 1)
+            .and_then(|g| g.add_edge(1, 2, 2))
+            .and_then(|g| g.add_edge_with(2, 0, || 3))
+            .expect("verts exist");
+
+        assert!(graph.undo());
+        assert_eq!(edges_of(&graph), [(0, 1, 1), (1, 2, 2)]);
+
+        assert!(graph.redo());
+        assert_eq!(edges_of(&graph), [(0, 1, 1), (1, 2, 2), (2, 3, 0)]);
+        assert!(!graph.redo());
+
+        graph.set_label(1, 0, 20)
+            .and_then(|g| g.remove_edge(0, 0))
+            .expect("edges exist");
+        assert_eq!(edges_of(&graph), [(1, 20, 2), (2, 3, 0)]);
+
+        assert!(graph.undo());
+        assert_eq!(edges_of(&graph), [(0, 1, 1), (1, 20, 2), (2, 3, 0)]);
+        assert!(graph.undo());
+        assert_eq!(edges_of(&graph), [(0, 1, 1), (1, 2, 2), (2, 3, 0)]);
+
+        // 3 edges and 3 verts remain
+        (0..6).for_each(|_| assert!(graph.undo()));
+        assert!(!graph.undo());
+        assert_eq!(graph.verts_cnt(), 0);
+    }
+
+    #[test]
+    fn undo_data_change()
+    {
+        let mut graph = Graph::<&'static str, u32>::new();
+        graph.enable_journal();
+        {
+            let mut vert = graph.add_vert("first");
+            **vert = "changed";
+            let data: &mut &str = &mut vert;
+            *data = "changed twice";
+        }
+        assert_eq!(**graph.get_vert(0).expect("vert exists"), "changed twice");
+
+        // both changes were made through the same handle
+        assert!(graph.undo());
+        assert_eq!(**graph.get_vert(0).expect("vert exists"), "first");
+        assert!(graph.redo());
+        assert_eq!(**graph.get_vert(0).expect("vert exists"), "changed twice");
+    }
+
+    #[test]
+    fn checkpoints()
+    {
+        let mut graph = Graph::<&'static str, u32>::new();
+        assert_eq!(graph.checkpoint(), None);
+
+        graph.add_vert("not journaled");
+        graph.enable_journal();
+        let start = graph.checkpoint().expect("journal is enabled");
+
+        graph.extend(["a", "b"]);
+        let verts_added = graph.checkpoint().expect("journal is enabled");
+        graph.add_edge(1, 2, 12)
+            .and_then(|g| g.add_edge(2, 0, 20))
+            .expect("verts exist");
+
+        assert!(graph.rollback_to(verts_added));
+        assert!(edges_of(&graph).is_empty());
+        assert_eq!(graph.verts_cnt(), 3);
+
+        // history after 'verts_added' is rewritten, so redone edges can't be returned to
+        graph.add_edge(0, 0, 0);
+        assert!(graph.rollback_to(start));
+        assert_eq!(graph.verts_cnt(), 1);
+        assert!(!graph.rollback_to(verts_added));
+        assert!(graph.redo());
+
+        graph.disable_journal();
+        assert!(!graph.undo());
+        assert!(!graph.rollback_to(start));
+    }
+
+    #[test]
+    fn checkpoints_of_other_sessions()
+    {
+        let mut graph = Graph::<&'static str, u32>::new();
+        graph.enable_journal();
+        let empty = graph.checkpoint().expect("journal is enabled");
+        graph.add_vert("a");
+        let one = graph.checkpoint().expect("journal is enabled");
+
+        // the new history looks the same, but it's not the one checkpoints were made in
+        graph.disable_journal();
+        graph.enable_journal();
+        graph.add_vert("b");
+        graph.add_vert("c");
+        assert!(!graph.rollback_to(empty));
+        assert!(!graph.rollback_to(one));
+        assert_eq!(graph.verts_cnt(), 3);
+
+        let mut other = Graph::<&'static str, u32>::new();
+        other.enable_journal();
+        other.add_vert("a");
+        assert!(!other.rollback_to(empty));
+        assert_eq!(other.verts_cnt(), 1);
+    }
+}
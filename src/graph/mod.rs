@@ -10,24 +10,45 @@ pub use serialize::{to_tgf, Unit};
 mod deserialize;
 pub use deserialize::read_tgf;
 
+mod journal;
+pub use journal::Checkpoint;
+use journal::{Change, Journal};
+
+mod transaction;
+pub use transaction::{Transaction, TransactionError};
+
+mod observe;
+pub use observe::{GraphEvent, ObserverId};
+use observe::Observers;
+
+mod snapshot;
+pub use snapshot::Snapshot;
+use snapshot::Verts;
+
+mod concurrent;
+pub use concurrent::ConcurrentGraph;
+
 use std::{ops::{Deref, DerefMut}, fmt::{Debug, Formatter, self, Write, Display}, collections::VecDeque};
 
 pub struct Graph<VertData, EdgeLabel>
 {
-    verts: Vec<Vert<VertData, EdgeLabel>>,
+    verts: Verts<VertData, EdgeLabel>,
+    journal: Option<Journal<VertData, EdgeLabel>>,
+    observers: Observers<VertData, EdgeLabel>,
 }
 
 impl<VD, EL> Graph<VD, EL>
 {
     pub fn new() -> Self
-    { Graph{ verts: vec![] } }
+    { Graph{ verts: Verts::new(), journal: None, observers: Observers::default() } }
 
 // add vert
     pub fn add_vert_with<F>(&mut self, producer: F) -> VertHandleMut<'_, VD, EL>
         where F: FnOnce() -> VD
     {
         let new_id = self.verts.len();
-        self.verts.push(Vert::new(new_id, producer()));
+        let change = self.push_vert(producer());
+        self.record(change);
         VertHandleMut::new(self, new_id)
     }
 
@@ -64,21 +85,90 @@ impl<VD, EL> Graph<VD, EL>
     pub fn verts_cnt(&self) -> usize
     { self.verts.len() }
 
+// change vert
+    /// Replaces data of the verticle `id`
+    pub fn set_data(&mut self, id: usize, data: VD) -> Option<&mut Self>
+    {
+        let change = self.swap_data(id, data)?;
+        self.record(change);
+        Some(self)
+    }
+
 // add edge
     pub fn add_edge_with<F>(&mut self, from: usize, to: usize, producer: F) -> Option<&mut Self>
         where F: FnOnce() -> EL
     {
-        let to_id = self.get_vert(to)
-            .map(|v_to| v_to.id);
-
-        self.get_vert_mut(from)
-            .and_then(|mut v_from| to_id.map(|to_id| v_from.add_edge_with(to_id, producer)))
-            .map(|()| self)
+        let change = self.push_edge_with(from, to, producer)?;
+        self.record(change);
+        Some(self)
     }
 
     pub fn add_edge(&mut self, from: usize, to: usize, label: EL) -> Option<&mut Self>
     { self.add_edge_with(from, to, move || label) }
 
+// change edge
+    /// Replaces label of `edge_idx`-th edge going from `from`
+    pub fn set_label(&mut self, from: usize, edge_idx: usize, label: EL) -> Option<&mut Self>
+    {
+        let change = self.swap_label(from, edge_idx, label)?;
+        self.record(change);
+        Some(self)
+    }
+
+    /// Removes `edge_idx`-th edge going from `from`, indices of the following edges are shifted
+    pub fn remove_edge(&mut self, from: usize, edge_idx: usize) -> Option<&mut Self>
+    {
+        let change = self.take_edge(from, edge_idx)?;
+        self.record(change);
+        Some(self)
+    }
+
+// mutations, which are up to the caller to record
+    fn push_vert(&mut self, data: VD) -> Change<VD, EL>
+    {
+        let new_id = self.verts.len();
+        self.verts.push(Vert::new(new_id, data));
+        Change::InsertVert(new_id)
+    }
+
+    fn swap_data(&mut self, id: usize, mut data: VD) -> Option<Change<VD, EL>>
+    {
+        std::mem::swap(&mut **self.verts.get_mut(id)?, &mut data);
+        Some(Change::Data{ id, data })
+    }
+
+    fn push_edge_with<F>(&mut self, from: usize, to: usize, producer: F) -> Option<Change<VD, EL>>
+        where F: FnOnce() -> EL
+    {
+        let to_id = self.get_vert(to)
+            .map(|v_to| v_to.id)?;
+
+        let v_from = self.verts.get_mut(from)?;
+        let idx = v_from.edges_cnt();
+        v_from.add_edge_with(to_id, producer);
+        Some(Change::InsertEdge{ from, idx })
+    }
+
+    fn swap_label(&mut self, from: usize, idx: usize, label: EL) -> Option<Change<VD, EL>>
+    {
+        let label = self.verts.get_mut(from)?
+            .edges.get_mut(idx)?
+            .replace_label(label);
+        Some(Change::Label{ from, idx, label })
+    }
+
+    fn take_edge(&mut self, from: usize, idx: usize) -> Option<Change<VD, EL>>
+    {
+        let v_from = self.verts.get_mut(from)?;
+        when! {
+            idx < v_from.edges_cnt() => {
+                let edge = v_from.edges.remove(idx);
+                Some(Change::RemoveEdge{ from, idx, edge })
+            },
+            _ => None,
+        }
+    }
+
 // traverse
     pub fn bfs(&self) -> BFSIterator<'_, VD, EL>
     {
@@ -92,7 +182,7 @@ impl<VD, EL> Graph<VD, EL>
 
     pub fn bfs_from<'gr>(&'gr self, from: VertHandle<'gr, VD, EL>) -> BFSIterator<'gr, VD, EL>
     {
-        assert!(std::ptr::eq(self, from.owner), "'from' is from  different owner");
+        assert!(from.is_of(self), "'from' is from  different owner");
 
         let mut marked = vec![false; self.verts.len()];
         marked[from.id] = true;
@@ -158,7 +248,9 @@ impl<VD, EL> Extend<VD> for Graph<VD, EL>
             .map(|(id, data)| (id + fst_new_id, data))
             .map(|(id, data)| Vert::new(id, data));
 
-        self.verts.extend(new_verts)
+        self.verts.extend(new_verts);
+        (fst_new_id..self.verts.len())
+            .for_each(|id| self.record(Change::InsertVert(id)))
     }
 }
 
@@ -200,7 +292,7 @@ pub struct VertHandle<'gr, VD, EL>
 impl<VD, EL> Clone for VertHandle<'_, VD, EL>
 {
     fn clone(&self) -> Self
-    { Self { ..*self } }
+    { *self }
 }
 
 impl<VD, EL> Copy for VertHandle<'_, VD, EL> {}
@@ -212,6 +304,16 @@ impl<'gr, VD, EL> VertHandle<'gr, VD, EL>
 
     pub fn edges(&self) -> EdgeIter<'gr, VD, EL>
     { EdgeIter{ graph: self.owner, from: self.vert, edge_idx: 0 } }
+
+    /// `idx`-th edge going from the verticle
+    pub fn edge(&self, idx: usize) -> Option<(&'gr EL, VertHandle<'gr, VD, EL>)>
+    {
+        self.vert.edges.get(idx)
+            .map(|edge| (edge.label(), VertHandle::new(self.owner, edge.to())))
+    }
+
+    pub (crate) fn is_of(&self, graph: &Graph<VD, EL>) -> bool
+    { std::ptr::eq(self.owner, graph) }
 }
 
 impl<VD, EL> Deref for VertHandle<'_, VD, EL>
@@ -227,14 +329,15 @@ pub struct VertHandleMut<'gr, VD, EL>
 {
     owner: &'gr mut Graph<VD, EL>,
     vert_id: usize,
+    touched: bool,
 }
 
 impl<'gr, VD, EL> VertHandleMut<'gr, VD, EL>
 {
     fn new(owner: &'gr mut Graph<VD, EL>, vert_id: usize) -> Self
-    { VertHandleMut{ owner, vert_id } }
+    { VertHandleMut{ owner, vert_id, touched: false } }
 
-    pub fn owner(&'gr mut self) -> &'gr mut Graph<VD, EL>
+    pub fn owner(&mut self) -> &mut Graph<VD, EL>
     { self.owner }
 }
 
@@ -253,11 +356,28 @@ impl<VD, EL> DerefMut for VertHandleMut<'_, VD, EL>
 {
     fn deref_mut(&mut self) -> &mut Self::Target
     {
+        // the data may be changed from now on, so journal has to save it
+        if !self.touched {
+            self.touched = true;
+            self.owner.record_data(self.vert_id);
+        }
+
         self.owner.verts.get_mut(self.vert_id)
             .expect("VertHanleMut must have been created on valid id")
     }
 }
 
+impl<VD, EL> Drop for VertHandleMut<'_, VD, EL>
+{
+    fn drop(&mut self)
+    {
+        // observers are notified once per handle, when the data can't be changed anymore
+        if self.touched {
+            self.owner.notify(GraphEvent::DataChanged(self.vert_id));
+        }
+    }
+}
+
 pub struct EdgeIter<'gr, VD, EL>
 {
     graph: &'gr Graph<VD, EL>,
@@ -325,11 +445,10 @@ impl<'gr, VD, EL> Iterator for BFSIterator<'gr, VD, EL>
                         !self.marked[v.id] => Some(v),
                         _ => None
                     }))
-                .map(|new_root| {
+                .inspect(|new_root| {
                     let new_root_id = new_root.id;
                     self.last_root = Some(new_root_id);
                     self.marked[new_root_id] = true;
-                    new_root
                 })
         }
     }
@@ -384,7 +503,7 @@ mod tests
     fn edges_simple() -> Result<(), String>
     {
         let mut graph = Graph::<(), &'static str>::new();
-        graph.extend(std::iter::repeat(()).take(5));
+        graph.extend(std::iter::repeat_n((), 5));
 
         let edges = [(0, "to self"), (1, "to one"), (2, "to two"), (1, "to one v.2"), (4, "to four")];
         edges.into_iter()
@@ -413,7 +532,7 @@ mod tests
     fn graph_of_size(size: usize) -> VoidGraph
     {
         let mut graph = VoidGraph::new();
-        graph.extend(std::iter::repeat(()).take(size));
+        graph.extend(std::iter::repeat_n((), size));
         graph
     }
 
@@ -452,7 +571,7 @@ mod tests
         let edges = [(0, 1), (1, 3), (3, 0), (3, 1), (4, 4), (2, 0), (1, 2)];
         edges.into_iter()
             .fold(&mut graph, |graph, (from, to)| graph.add_edge(from, to, ())
-                .expect(&format!("Couldn't add edge from {} to {}", from, to)));
+                .unwrap_or_else(|| panic!("Couldn't add edge from {} to {}", from, to)));
 
         test_bfs(graph)
     }
@@ -0,0 +1,251 @@
+use std::{mem, sync::{Mutex, PoisonError}};
+
+use super::Graph;
+
+/// Mutation of a [Graph] reported to its observers, see [Graph::observe]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GraphEvent
+{
+    VertAdded(usize),
+    /// The last verticle was removed, which only happens when its addition is undone
+    VertRemoved(usize),
+    /// Data was replaced, or changed through [VertHandleMut](super::VertHandleMut)
+    DataChanged(usize),
+    EdgeAdded{ from: usize, edge_idx: usize },
+    /// Edge was removed, indices of the following edges of `from` are shifted
+    EdgeRemoved{ from: usize, edge_idx: usize },
+    LabelChanged{ from: usize, edge_idx: usize },
+}
+
+/// Identifies observer registered with [Graph::observe]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(usize);
+
+type Observer<VD, EL> = Box<dyn FnMut(&Graph<VD, EL>, GraphEvent) + Send>;
+
+pub (crate) struct Observers<VD, EL>
+{
+    // only reached through `&mut`, so it's never locked, but it keeps the graph `Sync`
+    // while observers have to be just `Send`
+    observers: Mutex<Vec<(ObserverId, Observer<VD, EL>)>>,
+    next_id: usize,
+}
+
+impl<VD, EL> Observers<VD, EL>
+{
+    fn list(&mut self) -> &mut Vec<(ObserverId, Observer<VD, EL>)>
+    {
+        self.observers.get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<VD, EL> Default for Observers<VD, EL>
+{
+    fn default() -> Self
+    { Observers{ observers: Mutex::new(vec![]), next_id: 0 } }
+}
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Registers `observer`, which is called after every mutation of the graph
+    /// with the graph already mutated.
+    ///
+    /// Data changes made through [VertHandleMut](super::VertHandleMut) are reported once
+    /// the handle is dropped, if it was mutably dereferenced. Mutations made inside of [Graph::transaction]
+    /// are reported only if it succeeds, after all of them are applied.
+    /// Undo and redo report the mutations they make.
+    pub fn observe<F>(&mut self, observer: F) -> ObserverId
+        where F: FnMut(&Graph<VD, EL>, GraphEvent) + Send + 'static,
+    {
+        let id = ObserverId(self.observers.next_id);
+        self.observers.next_id += 1;
+        self.observers.list().push((id, Box::new(observer)));
+        id
+    }
+
+    /// Unregisters observer. Returns `false` if there was no such observer
+    pub fn unobserve(&mut self, id: ObserverId) -> bool
+    {
+        let observers = self.observers.list();
+        let old_cnt = observers.len();
+        observers.retain(|(observer_id, _)| *observer_id != id);
+        observers.len() != old_cnt
+    }
+
+    pub (crate) fn notify(&mut self, event: GraphEvent)
+    {
+        if self.observers.list().is_empty() {
+            return
+        }
+
+        // observers may look at the graph, so they can't be borrowed from it meanwhile
+        let observers = mem::take(&mut self.observers);
+        let mut guard = Notifying{ graph: self, observers };
+        let Notifying{ graph, observers } = &mut guard;
+        observers.list().iter_mut()
+            .for_each(|(_, observer)| observer(graph, event));
+    }
+}
+
+// returns taken observers to the graph even if one of them panics
+struct Notifying<'gr, VD, EL>
+{
+    graph: &'gr mut Graph<VD, EL>,
+    observers: Observers<VD, EL>,
+}
+
+impl<VD, EL> Drop for Notifying<'_, VD, EL>
+{
+    fn drop(&mut self)
+    { self.graph.observers = mem::take(&mut self.observers) }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    fn recording(graph: &mut Graph<u32, &'static str>) -> (ObserverId, Arc<Mutex<Vec<GraphEvent>>>)
+    {
+        let events = Arc::new(Mutex::new(vec![]));
+        let id = graph.observe({
+            let events = Arc::clone(&events);
+            move |_, event| events.lock().unwrap().push(event)
+        });
+        (id, events)
+    }
+
+    #[test]
+    fn events()
+    {
+        let mut graph = Graph::<u32, &'static str>::new();
+        let (id, events) = recording(&mut graph);
+
+        graph.extend([0, 1]);
+        graph.add_edge(0, 1, "one")
+            .and_then(|g| g.add_edge(1, 0, "zero"))
+            .and_then(|g| g.set_label(1, 0, "ZERO"))
+            .and_then(|g| g.remove_edge(0, 0))
+            .and_then(|g| g.set_data(0, 10))
+            .expect("verts and edges exist");
+
+        // reading through a handle isn't a mutation
+        let _ = **graph.get_vert_mut(1).expect("vert exists");
+        **graph.add_vert(2) += 20;
+
+        assert!(graph.unobserve(id));
+        assert!(!graph.unobserve(id));
+        graph.add_vert(3);
+
+        use GraphEvent::*;
+        assert_eq!(*events.lock().unwrap(), [
+            VertAdded(0),
+            VertAdded(1),
+            EdgeAdded{ from: 0, edge_idx: 0 },
+            EdgeAdded{ from: 1, edge_idx: 0 },
+            LabelChanged{ from: 1, edge_idx: 0 },
+            EdgeRemoved{ from: 0, edge_idx: 0 },
+            DataChanged(0),
+            VertAdded(2),
+            DataChanged(2),
+        ]);
+    }
+
+    #[test]
+    fn observer_sees_mutated_graph()
+    {
+        let mut graph = Graph::<u32, &'static str>::new();
+        let sum = Arc::new(Mutex::new(0));
+        graph.observe({
+            let sum = Arc::clone(&sum);
+            move |graph, _| *sum.lock().unwrap() = graph.verts().map(|v| **v).sum()
+        });
+
+        graph.extend([1, 2]);
+        assert_eq!(*sum.lock().unwrap(), 3);
+        graph.set_data(0, 10)
+            .expect("vert exists");
+        assert_eq!(*sum.lock().unwrap(), 12);
+
+        // handles report once the change is done
+        **graph.get_vert_mut(0).expect("vert exists") = 20;
+        assert_eq!(*sum.lock().unwrap(), 22);
+    }
+
+    #[test]
+    fn observers_are_kept()
+    {
+        let mut graph = Graph::<u32, &'static str>::new();
+        let (_, events) = recording(&mut graph);
+        // Cell is Send, but not Sync
+        let calls = std::cell::Cell::new(0);
+        let panicking = graph.observe(move |_, _| {
+            calls.set(calls.get() + 1);
+            assert_ne!(calls.get(), 2, "second call");
+        });
+
+        graph.add_vert(0);
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| graph.add_vert(1).id()));
+        assert!(res.is_err());
+        graph.add_vert(2);
+        assert!(graph.unobserve(panicking));
+        graph.add_vert(3);
+
+        use GraphEvent::*;
+        assert_eq!(*events.lock().unwrap(), [VertAdded(0), VertAdded(1), VertAdded(2), VertAdded(3)]);
+    }
+
+    #[test]
+    fn owner_of_new_vert()
+    {
+        let mut graph = Graph::<u32, &'static str>::new();
+        let (_, events) = recording(&mut graph);
+
+        let mut vert = graph.add_vert(1);
+        **vert += 1;
+        vert.owner().add_vert(3);
+        drop(vert);
+        graph.add_vert(4).owner().add_vert(5);
+        assert_eq!(graph.verts_cnt(), 4);
+
+        use GraphEvent::*;
+        assert_eq!(*events.lock().unwrap(), [VertAdded(0), VertAdded(1), DataChanged(0), VertAdded(2), VertAdded(3)]);
+    }
+
+    #[test]
+    fn transactions_and_undo()
+    {
+        let mut graph = Graph::<u32, &'static str>::new();
+        graph.enable_journal();
+        let (_, events) = recording(&mut graph);
+
+        let _ = graph.transaction(|tx| {
+            tx.add_vert(0);
+            Err::<(), _>(())
+        });
+        assert!(events.lock().unwrap().is_empty());
+
+        graph.transaction(|tx| {
+                let v = tx.add_vert(0);
+                tx.add_edge(v, v, "loop")
+                    .map(|_| ())
+                    .ok_or(())
+            })
+            .expect("transaction should succeed");
+        graph.undo();
+        graph.undo();
+        graph.redo();
+
+        use GraphEvent::*;
+        assert_eq!(*events.lock().unwrap(), [
+            VertAdded(0),
+            EdgeAdded{ from: 0, edge_idx: 0 },
+            EdgeRemoved{ from: 0, edge_idx: 0 },
+            VertRemoved(0),
+            VertAdded(0),
+        ]);
+    }
+}
@@ -0,0 +1,317 @@
+use std::{ops::{Deref, Index, IndexMut}, sync::{Arc, OnceLock}, fmt::{Debug, Display}};
+
+use super::{Graph, Vert};
+
+type CopyVert<VD, EL> = fn(&Vert<VD, EL>) -> Vert<VD, EL>;
+
+// verticles in a chunk of the table, every chunk but the last one is full
+const CHUNK: usize = 64;
+
+type Chunk<VD, EL> = Arc<Vec<Arc<Vert<VD, EL>>>>;
+
+/// Storage of verticles, which may be shared between a [Graph] and its [Snapshot]s.
+///
+/// The table of verticles is split into chunks, and the table, every chunk and every verticle
+/// are shared. So writing to a shared storage copies the table of chunks (one pointer per
+/// 64 verticles), the chunk being changed and only the verticle being changed, with its data and edges.
+pub (crate) struct Verts<VD, EL>
+{
+    chunks: Arc<Vec<Chunk<VD, EL>>>,
+    // storage can only become shared through 'share', which also sets this
+    copy_vert: OnceLock<CopyVert<VD, EL>>,
+}
+
+impl<VD, EL> Verts<VD, EL>
+{
+    pub (crate) fn new() -> Self
+    { Verts{ chunks: Arc::new(vec![]), copy_vert: OnceLock::new() } }
+
+    pub (crate) fn share(&self) -> Self
+        where VD: Clone,
+              EL: Clone,
+    {
+        self.copy_vert.get_or_init(|| Vert::clone);
+        self.reshare()
+    }
+
+    /// Same as [Verts::share], but only for a storage, which is already shared
+    fn reshare(&self) -> Self
+    {
+        assert!(self.copy_vert.get().is_some(), "storage must have been shared already");
+        Verts{ chunks: Arc::clone(&self.chunks), copy_vert: self.copy_vert.clone() }
+    }
+
+    pub (crate) fn len(&self) -> usize
+    {
+        self.chunks.last()
+            .map_or(0, |last| (self.chunks.len() - 1) * CHUNK + last.len())
+    }
+
+    pub (crate) fn iter(&self) -> impl Iterator<Item = &Vert<VD, EL>>
+    {
+        self.chunks.iter()
+            .flat_map(|chunk| chunk.iter())
+            .map(Deref::deref)
+    }
+
+    pub (crate) fn get(&self, id: usize) -> Option<&Vert<VD, EL>>
+    {
+        self.chunks.get(id / CHUNK)?
+            .get(id % CHUNK)
+            .map(Deref::deref)
+    }
+
+    pub (crate) fn get_mut(&mut self, id: usize) -> Option<&mut Vert<VD, EL>>
+    {
+        let copy_vert = self.copy_vert.get().copied();
+        let chunk = Arc::make_mut(&mut self.chunks)
+            .get_mut(id / CHUNK)?;
+        let vert = Arc::make_mut(chunk)
+            .get_mut(id % CHUNK)?;
+
+        if Arc::get_mut(vert).is_none() {
+            let copy_vert = copy_vert.expect("verticles can't be shared without a way to copy them");
+            *vert = Arc::new(copy_vert(vert));
+        }
+        Arc::get_mut(vert)
+    }
+
+    pub (crate) fn push(&mut self, vert: Vert<VD, EL>)
+    {
+        let chunks = Arc::make_mut(&mut self.chunks);
+        match chunks.last_mut() {
+            Some(last) if last.len() < CHUNK => Arc::make_mut(last).push(Arc::new(vert)),
+            _ => {
+                let mut chunk = Vec::with_capacity(CHUNK);
+                chunk.push(Arc::new(vert));
+                chunks.push(Arc::new(chunk));
+            },
+        }
+    }
+
+    pub (crate) fn pop(&mut self) -> Option<Vert<VD, EL>>
+    {
+        let copy_vert = self.copy_vert.get().copied();
+        let chunks = Arc::make_mut(&mut self.chunks);
+        let last = Arc::make_mut(chunks.last_mut()?);
+        let vert = last.pop()
+            .expect("chunks are never empty");
+        if last.is_empty() {
+            chunks.pop();
+        }
+
+        let vert = Arc::try_unwrap(vert)
+            .unwrap_or_else(|vert| {
+                let copy_vert = copy_vert.expect("verticles can't be shared without a way to copy them");
+                copy_vert(&vert)
+            });
+        Some(vert)
+    }
+
+    pub (crate) fn extend<It>(&mut self, iter: It)
+        where It: IntoIterator<Item = Vert<VD, EL>>
+    { iter.into_iter().for_each(|vert| self.push(vert)) }
+}
+
+impl<VD, EL> Index<usize> for Verts<VD, EL>
+{
+    type Output = Vert<VD, EL>;
+
+    fn index(&self, id: usize) -> &Self::Output
+    { &self.chunks[id / CHUNK][id % CHUNK] }
+}
+
+impl<VD, EL> IndexMut<usize> for Verts<VD, EL>
+{
+    fn index_mut(&mut self, id: usize) -> &mut Self::Output
+    {
+        self.get_mut(id)
+            .unwrap_or_else(|| panic!("no verticle with id {id}"))
+    }
+}
+
+/// Read-only copy of a [Graph] made by [Graph::snapshot]. It dereferences to [Graph],
+/// so everything that only reads a graph works with it.
+pub struct Snapshot<VD, EL>
+{
+    graph: Graph<VD, EL>,
+}
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Makes a snapshot of the graph's current state.
+    ///
+    /// The snapshot shares storage with the graph, so it's cheap to make.
+    /// Later mutations of the graph copy the table of chunks of 64 verticles and the chunks
+    /// they change (once per snapshot), and the verticles they change, and don't affect the snapshot.
+    /// Journal and observers of the graph are not shared.
+    pub fn snapshot(&self) -> Snapshot<VD, EL>
+        where VD: Clone,
+              EL: Clone,
+    {
+        let mut graph = Graph::new();
+        graph.verts = self.verts.share();
+        Snapshot{ graph }
+    }
+}
+
+impl<VD, EL> Snapshot<VD, EL>
+{
+    /// Turns the snapshot into a graph, which may be mutated. It still shares storage
+    /// with the original graph, which is copied on write the same way
+    pub fn into_graph(self) -> Graph<VD, EL>
+    { self.graph }
+}
+
+impl<VD, EL> Clone for Snapshot<VD, EL>
+{
+    fn clone(&self) -> Self
+    {
+        let mut graph = Graph::new();
+        graph.verts = self.graph.verts.reshare();
+        Snapshot{ graph }
+    }
+}
+
+impl<VD, EL> Deref for Snapshot<VD, EL>
+{
+    type Target = Graph<VD, EL>;
+
+    fn deref(&self) -> &Self::Target
+    { &self.graph }
+}
+
+impl<VD, EL> Debug for Snapshot<VD, EL>
+    where VD: Debug,
+          EL: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    { Debug::fmt(&self.graph, f) }
+}
+
+impl<VD, EL> Display for Snapshot<VD, EL>
+    where VD: Display,
+          EL: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    { Display::fmt(&self.graph, f) }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{Graph, to_tgf};
+
+    fn sample() -> Graph<String, u32>
+    {
+        let mut graph = Graph::new();
+        graph.extend(["a", "b", "c"].map(String::from));
+        graph.add_edge(0, 1, 1)
+            .and_then(|g| g.add_edge(1, 2, 2))
+            .expect("verts exist");
+        graph
+    }
+
+    #[test]
+    fn snapshot_is_isolated()
+    {
+        let mut graph = sample();
+        let snapshot = graph.snapshot();
+        let before = to_tgf(&snapshot);
+
+        graph.add_vert("d".into());
+        graph.add_edge(2, 3, 3)
+            .and_then(|g| g.set_label(0, 0, 10))
+            .and_then(|g| g.remove_edge(1, 0))
+            .expect("verts and edges exist");
+        graph.get_vert_mut(0)
+            .expect("vert exists")
+            .push_str("aa");
+
+        assert_eq!(to_tgf(&snapshot), before);
+        assert_eq!(snapshot.bfs().count(), 3);
+        assert_eq!(to_tgf(&graph), "1 aaa\n2 b\n3 c\n4 d\n#\n1 2 10\n3 4 3\n");
+    }
+
+    #[test]
+    fn unchanged_verts_are_shared()
+    {
+        let mut graph = sample();
+        let snapshot = graph.snapshot();
+        graph.add_edge(0, 2, 3)
+            .expect("verts exist");
+
+        let shared = |id| std::ptr::eq(
+            &*graph.get_vert(id).expect("vert exists"),
+            &*snapshot.get_vert(id).expect("vert exists"));
+        assert!(!shared(0));
+        assert!(shared(1));
+        assert!(shared(2));
+    }
+
+    #[test]
+    fn snapshots_of_snapshots()
+    {
+        let mut graph = sample();
+        let fst = graph.snapshot();
+        graph.add_vert("d".into());
+        let snd = graph.snapshot();
+        graph.add_vert("e".into());
+
+        let copy = fst.clone();
+        // snapshots are graphs too, so they can be snapshotted
+        let nested = fst.snapshot();
+        let mut detached = snd.into_graph();
+        detached.add_edge(3, 0, 30)
+            .expect("verts exist");
+
+        assert_eq!(copy.verts_cnt(), 3);
+        assert_eq!(nested.verts_cnt(), 3);
+        assert_eq!(fst.verts_cnt(), 3);
+        assert_eq!(detached.verts_cnt(), 4);
+        assert_eq!(graph.verts_cnt(), 5);
+        assert_eq!(graph.get_vert(3).expect("vert exists").edges_cnt(), 0);
+    }
+
+    #[test]
+    fn undo_after_snapshot()
+    {
+        let mut graph = sample();
+        graph.enable_journal();
+        graph.add_vert("d".into());
+        let snapshot = graph.snapshot();
+
+        assert!(graph.undo());
+        assert_eq!(graph.verts_cnt(), 3);
+        assert_eq!(snapshot.verts_cnt(), 4);
+        assert!(graph.redo());
+        assert_eq!(**graph.get_vert(3).expect("vert exists"), "d");
+    }
+
+    #[test]
+    fn many_chunks()
+    {
+        let mut graph = Graph::<usize, ()>::new();
+        graph.enable_journal();
+        graph.extend(0..200);
+        let snapshot = graph.snapshot();
+
+        // pops verticles of the last chunks and then changes a verticle of the first one
+        (0..140).for_each(|_| assert!(graph.undo()));
+        graph.set_data(10, 1000)
+            .expect("vert exists");
+        graph.add_vert(60);
+
+        assert_eq!(graph.verts_cnt(), 61);
+        assert_eq!(graph.verts().map(|v| **v).sum::<usize>(), (0..61).sum::<usize>() - 10 + 1000);
+        assert_eq!(snapshot.verts_cnt(), 200);
+        assert!(snapshot.verts().all(|v| **v == v.id()));
+
+        let shared = |id| std::ptr::eq(
+            &*graph.get_vert(id).expect("vert exists"),
+            &*snapshot.get_vert(id).expect("vert exists"));
+        assert!(!shared(10));
+        assert!(shared(11));
+        assert!(shared(59));
+    }
+}
@@ -0,0 +1,296 @@
+use std::{fmt::{Debug, Display}, error::Error, mem};
+
+use super::{Change, Graph};
+
+/// Handle to mutate a [Graph] inside of [Graph::transaction].
+///
+/// Mutations are applied to the graph right away, so [Transaction::graph] shows their result,
+/// but they are reverted if the transaction fails.
+/// Any failed mutation fails the whole transaction.
+pub struct Transaction<'gr, VD, EL>
+{
+    graph: &'gr mut Graph<VD, EL>,
+    changes: Vec<Change<VD, EL>>,
+    failure: Option<Failure>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Failure
+{
+    InvalidEdge(usize, usize),
+    MissingEdge(usize, usize),
+    MissingVert(usize),
+}
+
+#[derive(Debug)]
+pub enum TransactionError<E>
+{
+    /// Transaction's body returned an error
+    Aborted(E),
+    /// Edge from the first verticle to the second couldn't be added
+    InvalidEdge(usize, usize),
+    /// There is no edge with such index going from the verticle
+    MissingEdge{ from: usize, edge_idx: usize },
+    /// There is no verticle with such id
+    MissingVert(usize),
+}
+
+impl<E> Display for TransactionError<E>
+    where E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        use TransactionError::*;
+        match self {
+            Aborted(err) => f.write_fmt(format_args!("Transaction aborted: {err}")),
+            InvalidEdge(from, to) =>
+                f.write_fmt(format_args!("Cannot create edge from {from} to {to}. At least one of them is undefined")),
+            MissingEdge{ from, edge_idx } =>
+                f.write_fmt(format_args!("Verticle {from} doesn't have edge #{edge_idx}")),
+            MissingVert(id) => f.write_fmt(format_args!("Verticle {id} is undefined")),
+        }
+    }
+}
+
+impl<E> Error for TransactionError<E>
+    where E: Debug + Display,
+{}
+
+impl<'gr, VD, EL> Transaction<'gr, VD, EL>
+{
+    /// Current state of the graph, with all the mutations of this transaction applied
+    pub fn graph(&self) -> &Graph<VD, EL>
+    { self.graph }
+
+    /// Adds verticle and returns its id
+    pub fn add_vert_with<F>(&mut self, producer: F) -> usize
+        where F: FnOnce() -> VD
+    {
+        let new_id = self.graph.verts_cnt();
+        let change = self.graph.push_vert(producer());
+        self.changes.push(change);
+        new_id
+    }
+
+    /// Adds verticle and returns its id
+    pub fn add_vert(&mut self, data: VD) -> usize
+    { self.add_vert_with(move || data) }
+
+    pub fn set_data(&mut self, id: usize, data: VD) -> Option<&mut Self>
+    {
+        let change = self.graph.swap_data(id, data);
+        self.apply(change, Failure::MissingVert(id))
+    }
+
+    pub fn add_edge_with<F>(&mut self, from: usize, to: usize, producer: F) -> Option<&mut Self>
+        where F: FnOnce() -> EL
+    {
+        let change = self.graph.push_edge_with(from, to, producer);
+        self.apply(change, Failure::InvalidEdge(from, to))
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, label: EL) -> Option<&mut Self>
+    { self.add_edge_with(from, to, move || label) }
+
+    pub fn set_label(&mut self, from: usize, edge_idx: usize, label: EL) -> Option<&mut Self>
+    {
+        let change = self.graph.swap_label(from, edge_idx, label);
+        self.apply(change, Failure::MissingEdge(from, edge_idx))
+    }
+
+    pub fn remove_edge(&mut self, from: usize, edge_idx: usize) -> Option<&mut Self>
+    {
+        let change = self.graph.take_edge(from, edge_idx);
+        self.apply(change, Failure::MissingEdge(from, edge_idx))
+    }
+
+// private:
+    /// Records the changes to the journal, so there is nothing left to revert on drop
+    fn commit(&mut self)
+    {
+        mem::take(&mut self.changes).into_iter()
+            .for_each(|change| self.graph.record(change))
+    }
+
+    fn apply(&mut self, change: Option<Change<VD, EL>>, failure: Failure) -> Option<&mut Self>
+    {
+        match change {
+            Some(change) => {
+                self.changes.push(change);
+                Some(self)
+            },
+            None => {
+                // only the first failure is reported
+                self.failure.get_or_insert(failure);
+                None
+            },
+        }
+    }
+}
+
+impl<VD, EL> Drop for Transaction<'_, VD, EL>
+{
+    // reverts uncommitted changes, even if the body panicked
+    fn drop(&mut self)
+    {
+        mem::take(&mut self.changes).into_iter()
+            .rev()
+            .for_each(|change| { self.graph.revert(change); })
+    }
+}
+
+impl<VD, EL> Graph<VD, EL>
+{
+    /// Applies all the mutations made by `body` through [Transaction] atomically.
+    ///
+    /// If `body` returns an error or panics, or any of its mutations failed (even if `body` ignored that),
+    /// all the mutations are reverted and the graph is left untouched.
+    /// Otherwise mutations are recorded to the journal, if it's enabled, one by one.
+    pub fn transaction<T, E, F>(&mut self, body: F) -> Result<T, TransactionError<E>>
+        where F: FnOnce(&mut Transaction<'_, VD, EL>) -> Result<T, E>
+    {
+        let mut tx = Transaction{ graph: self, changes: vec![], failure: None };
+
+        let res = match (body(&mut tx), tx.failure) {
+            (Err(err), _) => Err(TransactionError::Aborted(err)),
+            (Ok(_), Some(Failure::InvalidEdge(from, to))) => Err(TransactionError::InvalidEdge(from, to)),
+            (Ok(_), Some(Failure::MissingEdge(from, edge_idx))) => Err(TransactionError::MissingEdge{ from, edge_idx }),
+            (Ok(_), Some(Failure::MissingVert(id))) => Err(TransactionError::MissingVert(id)),
+            (Ok(res), None) => Ok(res),
+        };
+
+        if res.is_ok() {
+            tx.commit();
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::test_util::edges_of;
+
+    use super::*;
+
+    fn sample() -> Graph<&'static str, u32>
+    {
+        let mut graph = Graph::new();
+        graph.extend(["a", "b"]);
+        graph.add_edge(0, 1, 1)
+            .expect("verts exist");
+        graph
+    }
+
+    #[test]
+    fn commit()
+    {
+        let mut graph = sample();
+        let new_id = graph.transaction(|tx| {
+            let c = tx.add_vert("c");
+            tx.add_edge(1, c, 12)
+                .and_then(|tx| tx.add_edge(c, 0, 20))
+                .and_then(|tx| tx.set_label(0, 0, 10))
+                .and_then(|tx| tx.set_data(0, "A"))
+                .ok_or("couldn't mutate")?;
+            assert_eq!(tx.graph().verts_cnt(), 3);
+            Ok::<_, &str>(c)
+        });
+
+        assert!(matches!(new_id, Ok(2)));
+        assert_eq!(edges_of(&graph), [(0, 10, 1), (1, 12, 2), (2, 20, 0)]);
+        assert_eq!(**graph.get_vert(0).expect("vert exists"), "A");
+    }
+
+    #[test]
+    fn rollback_on_error()
+    {
+        let mut graph = sample();
+        let res = graph.transaction(|tx| {
+            tx.add_vert("c");
+            tx.remove_edge(0, 0)
+                .and_then(|tx| tx.set_data(1, "B"))
+                .ok_or("couldn't mutate")?;
+            Err::<(), _>("changed my mind")
+        });
+
+        assert!(matches!(res, Err(TransactionError::Aborted("changed my mind"))));
+        assert_eq!(graph.verts_cnt(), 2);
+        assert_eq!(**graph.get_vert(1).expect("vert exists"), "b");
+        assert_eq!(edges_of(&graph), [(0, 1, 1)]);
+    }
+
+    #[test]
+    fn rollback_on_panic()
+    {
+        let mut graph = sample();
+        graph.enable_journal();
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            graph.transaction(|tx| {
+                tx.add_vert("c");
+                tx.set_label(0, 0, 10)
+                    .and_then(|tx| tx.add_edge(1, 0, 2))
+                    .expect("edge exists");
+                // there is no such verticle, so the body panics
+                tx.set_data(5, "f")
+                    .expect("vert exists");
+                Ok::<_, ()>(())
+            })
+        }));
+
+        assert!(res.is_err());
+        assert_eq!(graph.verts_cnt(), 2);
+        assert_eq!(edges_of(&graph), [(0, 1, 1)]);
+        // nothing was journaled either
+        assert!(!graph.undo());
+    }
+
+    #[test]
+    fn rollback_on_invalid_edge()
+    {
+        let mut graph = sample();
+        let res = graph.transaction(|tx| {
+            tx.add_edge(1, 0, 10);
+            // failure is ignored here, but it still rolls everything back
+            tx.add_edge(1, 5, 15);
+            tx.add_edge(0, 0, 0);
+            Ok::<_, ()>(())
+        });
+
+        assert!(matches!(res, Err(TransactionError::InvalidEdge(1, 5))));
+        assert_eq!(edges_of(&graph), [(0, 1, 1)]);
+
+        let res = graph.transaction(|tx| {
+            tx.set_label(1, 0, 10);
+            Ok::<_, ()>(())
+        });
+        assert!(matches!(res, Err(TransactionError::MissingEdge{ from: 1, edge_idx: 0 })));
+    }
+
+    #[test]
+    fn journaled_transaction()
+    {
+        let mut graph = sample();
+        graph.enable_journal();
+        let before = graph.checkpoint().expect("journal is enabled");
+
+        graph.transaction(|tx| {
+                let c = tx.add_vert("c");
+                tx.add_edge(c, c, 22)
+                    .map(|_| ())
+                    .ok_or(())
+            })
+            .expect("transaction should succeed");
+        assert_eq!(edges_of(&graph), [(0, 1, 1), (2, 22, 2)]);
+
+        let _ = graph.transaction(|tx| {
+            tx.add_vert("d");
+            Err::<(), _>(())
+        });
+        assert_eq!(graph.verts_cnt(), 3);
+
+        assert!(graph.rollback_to(before));
+        assert_eq!(graph.verts_cnt(), 2);
+        assert_eq!(edges_of(&graph), [(0, 1, 1)]);
+    }
+}
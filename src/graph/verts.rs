@@ -2,7 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use super::Edge;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Vert<VD, EL>
 {
     data: VD,
@@ -1,5 +1,11 @@
 mod graph;
 pub use graph::*;
 
+mod algo;
+pub use algo::*;
+
+#[cfg(test)]
+mod test_util;
+
 #[macro_use]
 extern crate kiam;
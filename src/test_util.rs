@@ -0,0 +1,39 @@
+use crate::{read_tgf, Graph, VertHandle};
+
+/// Graph of example/months.tgf
+pub (crate) fn months() -> Graph<String, String>
+{
+    read_tgf(include_str!("../example/months.tgf").lines())
+        .expect("example should be parsed")
+}
+
+/// Verticle of the `month` in [months]
+pub (crate) fn find<'gr>(graph: &'gr Graph<String, String>, month: &str) -> VertHandle<'gr, String, String>
+{
+    graph.verts()
+        .find(|vert| vert.as_str() == month)
+        .unwrap_or_else(|| panic!("there is {month}"))
+}
+
+/// Graph of `size` verticles with `edges` labeled by their index in it
+pub (crate) fn graph_with(size: usize, edges: &[(usize, usize)]) -> Graph<(), usize>
+{
+    let mut graph = Graph::new();
+    graph.extend(std::iter::repeat_n((), size));
+    edges.iter()
+        .enumerate()
+        .for_each(|(idx, (from, to))| {
+            graph.add_edge(*from, *to, idx)
+                .expect("verts exist");
+        });
+    graph
+}
+
+/// All the edges of `graph` as `(from, label, to)`
+pub (crate) fn edges_of<VD, EL: Clone>(graph: &Graph<VD, EL>) -> Vec<(usize, EL, usize)>
+{
+    graph.verts()
+        .flat_map(|from| from.edges()
+            .map(move |(label, to)| (from.id(), label.clone(), to.id())))
+        .collect()
+}